@@ -87,4 +87,211 @@ pub enum PokerError {
     
     #[msg("Pot not empty")]
     PotNotEmpty,
+
+    #[msg("Slot hashes sysvar unavailable")]
+    InvalidSlotHashes,
+
+    #[msg("Invalid seat")]
+    InvalidSeat,
+
+    #[msg("Seat already taken")]
+    SeatTaken,
+
+    #[msg("Player has been eliminated")]
+    PlayerEliminated,
+
+    #[msg("Refund account must be a writable player wallet")]
+    InvalidRefundAccount,
+
+    #[msg("Game settings can only change before players join")]
+    GameSettingsLocked,
+
+    #[msg("Idle timeout out of range")]
+    InvalidIdleTimeout,
+
+    #[msg("Unauthorized admin")]
+    UnauthorizedAdmin,
+
+    #[msg("Fee exceeds maximum")]
+    InvalidFee,
+
+    #[msg("Game type not allowed")]
+    GameTypeNotAllowed,
+
+    #[msg("Buy-in exceeds maximum")]
+    BuyInTooLarge,
+
+    #[msg("No pending admin transfer")]
+    NoPendingAdmin,
+
+    #[msg("Invalid treasury account")]
+    InvalidTreasury,
+
+    #[msg("Game server bond below minimum")]
+    InsufficientBond,
+
+    #[msg("Unauthorized arbiter")]
+    UnauthorizedArbiter,
+
+    #[msg("Slash amount exceeds bond")]
+    SlashExceedsBond,
+
+    #[msg("Compensation amounts don't match accounts")]
+    InvalidCompensationList,
+
+    #[msg("This game settles through propose_settlement")]
+    SettlementWindowRequired,
+
+    #[msg("This game has no settlement window")]
+    SettlementWindowDisabled,
+
+    #[msg("Settlement window out of range")]
+    InvalidSettlementWindow,
+
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+
+    #[msg("Challenge window still open")]
+    ChallengeWindowOpen,
+
+    #[msg("Settlement already disputed")]
+    SettlementDisputed,
+
+    #[msg("Settlement not disputed")]
+    SettlementNotDisputed,
+
+    #[msg("Payout accounts don't match settlement")]
+    PayoutAccountMismatch,
+
+    #[msg("Attested settlement is disabled for this game")]
+    AttestationDisabled,
+
+    #[msg("Invalid attestation threshold")]
+    InvalidAttestationThreshold,
+
+    #[msg("Not enough valid attestations")]
+    InsufficientAttestations,
+
+    #[msg("Malformed Ed25519 instruction")]
+    InvalidEd25519Instruction,
+
+    #[msg("Channel state must cover every seated player")]
+    InvalidChannelState,
+
+    #[msg("Channel state not signed by every player and the server")]
+    ChannelStateNotFullySigned,
+
+    #[msg("Channel state nonce is not newer")]
+    StaleChannelNonce,
+
+    #[msg("Too many delegates")]
+    TooManyDelegates,
+
+    #[msg("Invalid delegate permissions")]
+    InvalidDelegatePermissions,
+
+    #[msg("Invalid payout signer set")]
+    InvalidPayoutSigners,
+
+    #[msg("Not enough operator signatures for this amount")]
+    InsufficientPayoutSigners,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Unauthorized guardian")]
+    UnauthorizedGuardian,
+
+    #[msg("Player profile does not match the player")]
+    InvalidPlayerProfile,
+
+    #[msg("Rating accounts must cover every player in the game")]
+    RatingAccountsMismatch,
+
+    #[msg("Invalid season settings")]
+    InvalidSeason,
+
+    #[msg("Season is not running")]
+    SeasonNotLive,
+
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+
+    #[msg("Season account does not match the game")]
+    SeasonMismatch,
+
+    #[msg("Player is not allowed to join this table")]
+    TableAccessDenied,
+
+    #[msg("Player does not hold the token required for this table")]
+    TokenGateNotMet,
+
+    #[msg("Invalid token metadata account")]
+    InvalidTokenMetadata,
+
+    #[msg("Invalid token gate")]
+    InvalidTokenGate,
+
+    #[msg("Player is self-excluded")]
+    SelfExcluded,
+
+    #[msg("Buy-in exceeds the player's limits")]
+    BuyInLimitExceeded,
+
+    #[msg("Missing or invalid compliance attestation")]
+    InvalidComplianceAttestation,
+
+    #[msg("Compliance attestation has expired")]
+    ComplianceAttestationExpired,
+
+    #[msg("Bankroll does not belong to the player")]
+    InvalidBankroll,
+
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Signer is neither the player nor an authorized session key")]
+    UnauthorizedSession,
+
+    #[msg("Session has expired")]
+    SessionExpired,
+
+    #[msg("Session spend limit exceeded")]
+    SessionLimitExceeded,
+
+    #[msg("Invalid session settings")]
+    InvalidSession,
+
+    #[msg("Paying from the wallet needs the player's own signature")]
+    WalletSignatureRequired,
+
+    #[msg("Withdrawal is still time-locked")]
+    WithdrawalLocked,
+
+    #[msg("Invalid backing")]
+    InvalidBacking,
+
+    #[msg("Backing is not fully funded")]
+    BackingNotFunded,
+
+    #[msg("Backed player's game is still in progress")]
+    BackingNotSettleable,
+
+    #[msg("Invalid bounty settings")]
+    InvalidBounty,
+
+    #[msg("Eliminator must be another player still in the tournament")]
+    InvalidEliminator,
+
+    #[msg("Compensation must go to a player of the slashed game")]
+    InvalidCompensationRecipient,
+
+    #[msg("Settlement has not expired yet")]
+    SettlementNotExpired,
+
+    #[msg("No config change is pending")]
+    NoPendingConfigChange,
+
+    #[msg("Config change is still time-locked")]
+    ConfigChangeLocked,
 }
\ No newline at end of file
@@ -0,0 +1,31 @@
+// programs/poker_escrow/src/instructions/accept_admin.rs
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Step two: the nominated admin takes over
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let pending_admin = config.pending_admin.ok_or(PokerError::NoPendingAdmin)?;
+    require_keys_eq!(pending_admin, ctx.accounts.new_admin.key(), PokerError::UnauthorizedAdmin);
+
+    let previous_admin = config.admin;
+    config.admin = pending_admin;
+    config.pending_admin = None;
+
+    msg!("Admin transferred: {} -> {}", previous_admin, config.admin);
+
+    Ok(())
+}
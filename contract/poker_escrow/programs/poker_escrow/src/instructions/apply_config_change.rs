@@ -0,0 +1,38 @@
+// programs/poker_escrow/src/instructions/apply_config_change.rs
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct ApplyConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler(ctx: Context<ApplyConfigChange>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let change = config.pending_change.take().ok_or(PokerError::NoPendingConfigChange)?;
+    require!(
+        Clock::get()?.unix_timestamp >= change.applies_at,
+        PokerError::ConfigChangeLocked
+    );
+
+    if let Some(fee_bps) = change.fee_bps {
+        config.fee_bps = fee_bps;
+    }
+    if let Some(arbiter) = change.arbiter {
+        config.arbiter = arbiter;
+    }
+
+    msg!(
+        "Config change applied: fee={} bps, arbiter={}",
+        config.fee_bps,
+        config.arbiter
+    );
+
+    Ok(())
+}
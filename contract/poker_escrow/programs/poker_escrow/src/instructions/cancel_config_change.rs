@@ -0,0 +1,31 @@
+// programs/poker_escrow/src/instructions/cancel_config_change.rs
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    /// Guardian only, like cancelling a treasury withdrawal
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = guardian @ PokerError::UnauthorizedGuardian,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let change = config.pending_change.take().ok_or(PokerError::NoPendingConfigChange)?;
+
+    msg!(
+        "Config change cancelled: fee={:?} bps, arbiter={:?}",
+        change.fee_bps,
+        change.arbiter
+    );
+
+    Ok(())
+}
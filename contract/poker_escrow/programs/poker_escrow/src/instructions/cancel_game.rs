@@ -0,0 +1,72 @@
+// programs/poker_escrow/src/instructions/cancel_game.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, GameServerProfile};
+use crate::errors::PokerError;
+use crate::utils::refund_players;
+
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    /// CHECK: Game server owner, receives rent when the escrow closes
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (player payout account, player profile) pairs to
+    // refund, both writable. Large tables can be cancelled across several
+    // transactions, each refunding a chunk.
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelGame<'info>>) -> Result<()> {
+    let game_id = ctx.accounts.poker_escrow.game_id;
+    let current_status = ctx.accounts.poker_escrow.status.clone();
+
+    // Only games that never started can be cancelled. A game already marked
+    // Abandoned is a cancellation still in progress (chunked refunds).
+    require!(
+        current_status == GameStatus::Pending || current_status == GameStatus::Abandoned,
+        PokerError::GameAlreadyStarted
+    );
+
+    // Stop new players from joining while refunds are processed
+    ctx.accounts.poker_escrow.status = GameStatus::Abandoned;
+
+    let refunded = refund_players(&mut ctx.accounts.poker_escrow, ctx.remaining_accounts)?;
+    let players_left = ctx.accounts.poker_escrow.current_players;
+
+    msg!(
+        "Poker game {} cancelled: refunded {} lamports, {} players awaiting refund",
+        game_id,
+        refunded,
+        players_left
+    );
+
+    // Close escrow and return rent to game server owner once everyone is refunded
+    if players_left == 0 {
+        ctx.accounts.poker_escrow.close(ctx.accounts.server_authority.to_account_info())?;
+        msg!("Poker game {} closed", game_id);
+    }
+
+    Ok(())
+}
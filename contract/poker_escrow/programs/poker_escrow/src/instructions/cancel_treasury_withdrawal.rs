@@ -0,0 +1,47 @@
+// programs/poker_escrow/src/instructions/cancel_treasury_withdrawal.rs
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Treasury, WithdrawalRequest};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct CancelTreasuryWithdrawal<'info> {
+    /// Guardian only: the admin requested the withdrawal, so cancelling it
+    /// is the guardian's check on a compromised admin
+    pub guardian: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = guardian @ PokerError::UnauthorizedGuardian,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        close = requested_by,
+        seeds = [WithdrawalRequest::SEED_PREFIX, treasury.key().as_ref()],
+        bump = withdrawal_request.bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    /// CHECK: Paid the request's rent, gets it back
+    #[account(mut, address = withdrawal_request.requested_by)]
+    pub requested_by: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelTreasuryWithdrawal>) -> Result<()> {
+    msg!(
+        "Treasury withdrawal of {} lamports to {} cancelled by {}",
+        ctx.accounts.withdrawal_request.amount,
+        ctx.accounts.withdrawal_request.destination,
+        ctx.accounts.guardian.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+// programs/poker_escrow/src/instructions/cancel_withdrawal.rs
+use anchor_lang::prelude::*;
+use crate::state::{Bankroll, WithdrawalRequest};
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    /// Only the wallet itself, never a session key, can cancel
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [Bankroll::SEED_PREFIX, player.key().as_ref()],
+        bump = bankroll.bump,
+        has_one = player,
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+
+    #[account(
+        mut,
+        close = requested_by,
+        seeds = [WithdrawalRequest::SEED_PREFIX, bankroll.key().as_ref()],
+        bump = withdrawal_request.bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    /// CHECK: Paid the request's rent, gets it back
+    #[account(mut, address = withdrawal_request.requested_by)]
+    pub requested_by: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    msg!(
+        "Player {} cancelled a withdrawal of {} lamports",
+        ctx.accounts.player.key(),
+        ctx.accounts.withdrawal_request.amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// programs/poker_escrow/src/instructions/challenge_channel.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{PokerEscrow, GameStatus, Channel, StackUpdate, GameServerProfile};
+use crate::errors::PokerError;
+use crate::utils::require_fully_signed;
+
+#[derive(Accounts)]
+pub struct ChallengeChannel<'info> {
+    /// Anyone holding a newer fully signed state can challenge
+    pub challenger: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        mut,
+        seeds = [Channel::SEED_PREFIX, poker_escrow.key().as_ref()],
+        bump = channel.bump,
+        has_one = poker_escrow,
+    )]
+    pub channel: Account<'info, Channel>,
+
+    /// CHECK: Instructions sysvar, used to read the Ed25519 precompile instructions
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ChallengeChannel>, nonce: u64, stacks: Vec<StackUpdate>) -> Result<()> {
+    let clock = Clock::get()?;
+    let poker_escrow = &ctx.accounts.poker_escrow;
+
+    require!(poker_escrow.status == GameStatus::Settling, PokerError::GameNotActive);
+    require!(
+        clock.unix_timestamp <= ctx.accounts.channel.challenge_deadline,
+        PokerError::ChallengeWindowClosed
+    );
+    require!(nonce > ctx.accounts.channel.nonce, PokerError::StaleChannelNonce);
+
+    Channel::validate_stacks(poker_escrow, &stacks)?;
+    require_fully_signed(
+        poker_escrow,
+        &ctx.accounts.instructions.to_account_info(),
+        &ctx.accounts.game_server,
+        &ctx.accounts.challenger.key(),
+        &Channel::state_message(&poker_escrow.key(), nonce, &stacks),
+    )?;
+
+    // Newer state replaces the old one and restarts the window so the
+    // other parties can respond with anything newer still
+    let channel = &mut ctx.accounts.channel;
+    let previous_nonce = channel.nonce;
+    channel.nonce = nonce;
+    channel.stacks = stacks;
+    channel.challenge_deadline = clock.unix_timestamp
+        .checked_add(poker_escrow.settlement_window)
+        .ok_or(PokerError::MathOverflow)?;
+
+    msg!(
+        "Channel for poker game {} challenged by {}: nonce {} -> {}, challenge until {}",
+        poker_escrow.game_id,
+        ctx.accounts.challenger.key(),
+        previous_nonce,
+        nonce,
+        channel.challenge_deadline
+    );
+
+    Ok(())
+}
@@ -1,16 +1,24 @@
 // programs/poker_escrow/src/instructions/close_game.rs
 use anchor_lang::prelude::*;
-use crate::state::{PokerEscrow, GameStatus, GameType};
+use crate::state::{PokerEscrow, GameStatus, GameType, GameServerProfile};
 use crate::errors::PokerError;
+use crate::utils::require_payout_cosigners;
 
 #[derive(Accounts)]
 pub struct CloseGame<'info> {
     #[account(mut)]
-    pub game_server: Signer<'info>,
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
 
     #[account(
         mut,
-        close = game_server,
+        close = server_authority,
         seeds = [
             PokerEscrow::SEED_PREFIX,
             game_server.key().as_ref(),
@@ -21,10 +29,16 @@ pub struct CloseGame<'info> {
     )]
     pub poker_escrow: Account<'info, PokerEscrow>,
 
+    /// CHECK: Game server owner, receives rent when the escrow closes
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
+    // remaining_accounts: co-signing payout signers, when the escrow
+    // balance requires them
 }
 
-pub fn handler(ctx: Context<CloseGame>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseGame<'info>>) -> Result<()> {
     let poker_escrow = &ctx.accounts.poker_escrow;
     let game_id = poker_escrow.game_id;
     let game_type = poker_escrow.game_type.clone();
@@ -33,13 +47,22 @@ pub fn handler(ctx: Context<CloseGame>) -> Result<()> {
     require!(poker_escrow.status == GameStatus::Completed, PokerError::GameNotCompleted);
     require!(poker_escrow.total_pot == 0, PokerError::PotNotEmpty);
 
+    // Everything left in the escrow goes to the server owner on close
+    require_payout_cosigners(
+        poker_escrow,
+        &ctx.accounts.operator.key(),
+        ctx.remaining_accounts,
+        poker_escrow.to_account_info().lamports(),
+    )?;
+
     // Only allow closing Tournament and CashGame accounts manually
     // SingleHand games auto-close in distribute_pot
     match game_type {
-        GameType::Tournament | GameType::CashGame => {
+        GameType::Tournament | GameType::KnockoutTournament | GameType::CashGame => {
             msg!("Closing {} poker game {} with {} hands played", 
                  match game_type {
                      GameType::Tournament => "Tournament",
+                     GameType::KnockoutTournament => "Knockout Tournament",
                      GameType::CashGame => "Cash Game",
                      _ => "Unknown"
                  },
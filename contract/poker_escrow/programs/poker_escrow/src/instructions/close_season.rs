@@ -0,0 +1,88 @@
+// programs/poker_escrow/src/instructions/close_season.rs
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Season};
+use crate::errors::PokerError;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct CloseSeason<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PokerError::UnauthorizedAdmin,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [Season::SEED_PREFIX, season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+
+    /// CHECK: Protocol treasury, receives prizes nobody placed for
+    #[account(mut, address = config.treasury @ PokerError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+    // remaining_accounts: wallets of the players in `season.standings`
+    // (writable), in standings order
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseSeason<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let season = &ctx.accounts.season;
+
+    require!(!season.closed, PokerError::SeasonNotLive);
+    require!(clock.unix_timestamp >= season.ends_at, PokerError::SeasonNotEnded);
+    require!(
+        ctx.remaining_accounts.len() == season.standings.len(),
+        PokerError::PayoutAccountMismatch
+    );
+
+    // Pay each place its share of the pool
+    let season_info = season.to_account_info();
+    let prize_pool = season.prize_pool;
+    let mut paid: u64 = 0;
+    for ((standing, bps), wallet) in season.standings
+        .iter()
+        .zip(season.prize_bps.iter())
+        .zip(ctx.remaining_accounts.iter())
+    {
+        require_keys_eq!(standing.player, *wallet.key, PokerError::PayoutAccountMismatch);
+        require!(wallet.is_writable, PokerError::PayoutAccountMismatch);
+
+        let prize = ProgramConfig::fee_for(prize_pool, *bps)?;
+        transfer_lamports(&season_info, wallet, prize)?;
+        paid += prize;
+
+        msg!("Season {} prize of {} lamports to {}", season.season_id, prize, standing.player);
+    }
+
+    // Unclaimed places and rounding dust go to the treasury
+    let leftover = prize_pool - paid;
+    if leftover > 0 {
+        transfer_lamports(&season_info, &ctx.accounts.treasury.to_account_info(), leftover)?;
+    }
+
+    let season_key = season.key();
+    let season = &mut ctx.accounts.season;
+    season.prize_pool = 0;
+    season.closed = true;
+
+    let config = &mut ctx.accounts.config;
+    if config.current_season == Some(season_key) {
+        config.current_season = None;
+    }
+
+    msg!(
+        "Season {} closed: {} lamports paid in prizes, {} to treasury",
+        season.season_id,
+        paid,
+        leftover
+    );
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// programs/poker_escrow/src/instructions/crank_refund.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, GameServerProfile};
+use crate::errors::PokerError;
+use crate::utils::refund_players;
+
+#[derive(Accounts)]
+pub struct CrankRefund<'info> {
+    /// Anyone can crank refunds (e.g. a keeper bot), they only pay the fee
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    /// CHECK: Game server owner, receives rent on close
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (recorded payout account, player profile) pairs to
+    // refund, both writable
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CrankRefund<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game_id = ctx.accounts.poker_escrow.game_id;
+
+    // Same conditions as emergency_refund, but deposits can only ever go
+    // back to the accounts recorded in PlayerDeposit.payout_to
+    require!(
+        ctx.accounts.poker_escrow.is_refundable(clock.unix_timestamp),
+        PokerError::RefundTimeoutNotReached
+    );
+
+    // Once refunds start the game can no longer be joined or played
+    ctx.accounts.poker_escrow.status = GameStatus::Abandoned;
+
+    let refunded = refund_players(&mut ctx.accounts.poker_escrow, ctx.remaining_accounts)?;
+    let players_left = ctx.accounts.poker_escrow.current_players;
+
+    msg!(
+        "Refund crank on poker game {} by {}: refunded {} lamports, {} players left",
+        game_id,
+        ctx.accounts.cranker.key(),
+        refunded,
+        players_left
+    );
+
+    // Close escrow and return rent to game server owner once the last deposit is returned
+    if players_left == 0 {
+        ctx.accounts.poker_escrow.close(ctx.accounts.server_authority.to_account_info())?;
+        msg!("Poker game {} closed", game_id);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+// programs/poker_escrow/src/instructions/create_backing.rs
+use anchor_lang::prelude::*;
+use crate::state::{Backer, BackerTerms, Backing, GameStatus, PokerEscrow};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct CreateBacking<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(constraint = poker_escrow.status == GameStatus::Pending @ PokerError::GameNotPending)]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        init,
+        payer = player,
+        space = Backing::MAX_SIZE,
+        seeds = [Backing::SEED_PREFIX, poker_escrow.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub backing: Account<'info, Backing>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateBacking>, backers: Vec<BackerTerms>) -> Result<()> {
+    Backing::validate(&backers, ctx.accounts.poker_escrow.buy_in)?;
+
+    let backing = &mut ctx.accounts.backing;
+    backing.poker_escrow = ctx.accounts.poker_escrow.key();
+    backing.player = ctx.accounts.player.key();
+    backing.backers = backers
+        .into_iter()
+        .map(|terms| Backer { terms, funded: false })
+        .collect();
+    backing.joined = false;
+    backing.created_at = Clock::get()?.unix_timestamp;
+    backing.bump = ctx.bumps.backing;
+
+    msg!(
+        "Player {} offered action in game {} to {} backers",
+        backing.player,
+        ctx.accounts.poker_escrow.game_id,
+        backing.backers.len()
+    );
+
+    Ok(())
+}
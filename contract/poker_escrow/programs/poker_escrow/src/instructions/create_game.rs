@@ -1,17 +1,25 @@
 // programs/poker_escrow/src/instructions/create_game.rs
 use anchor_lang::prelude::*;
-use crate::state::{PokerEscrow, GameStatus, GameType};
+use crate::state::{PokerEscrow, GameStatus, GameType, ProgramConfig, GameServerProfile, JoinRules, TokenGate, ComplianceAttestation};
 use crate::errors::PokerError;
 
 #[derive(Accounts)]
 #[instruction(game_id: u64)]
 pub struct CreateGame<'info> {
     #[account(mut)]
-    pub game_server: Signer<'info>,
+    pub operator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
     
     #[account(
         init,
-        payer = game_server,
+        payer = operator,
         space = PokerEscrow::MAX_SIZE,
         seeds = [
             PokerEscrow::SEED_PREFIX,
@@ -22,9 +30,17 @@ pub struct CreateGame<'info> {
     )]
     pub poker_escrow: Account<'info, PokerEscrow>,
     
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateGame>,
     game_id: u64,
@@ -33,6 +49,7 @@ pub fn handler(
     max_players: u8,
     game_type: GameType,
     hand_identifier: String,
+    rules: JoinRules,
 ) -> Result<()> {
     // Validate inputs
     require!(min_players >= 2, PokerError::InvalidPlayerCount);
@@ -41,6 +58,31 @@ pub fn handler(
     require!(buy_in > 0, PokerError::IncorrectBuyIn);
     require!(!hand_identifier.is_empty(), PokerError::InvalidHandIdentifier);
     require!(hand_identifier.len() <= 64, PokerError::InvalidHandIdentifier);
+    if let Some(TokenGate::Mint { min_amount, .. }) = &rules.token_gate {
+        require!(*min_amount > 0, PokerError::InvalidTokenGate);
+    }
+    let attestation_flags = if rules.required_attester.is_some() {
+        rules.required_attestation_flags
+    } else {
+        0
+    };
+    require!(
+        attestation_flags & !ComplianceAttestation::ALL_FLAGS == 0,
+        PokerError::InvalidComplianceAttestation
+    );
+
+    // Enforce protocol-wide limits
+    let config = &ctx.accounts.config;
+    require!(config.allows_game_type(&game_type), PokerError::GameTypeNotAllowed);
+    require!(buy_in <= config.max_buy_in, PokerError::BuyInTooLarge);
+    let fee_bps = config.fee_bps;
+
+    // Only bonded servers can host games
+    let profile = &mut ctx.accounts.game_server;
+    require!(profile.bond >= config.min_server_bond, PokerError::InsufficientBond);
+    profile.games_hosted = profile.games_hosted
+        .checked_add(1)
+        .ok_or(PokerError::MathOverflow)?;
 
     let poker_escrow = &mut ctx.accounts.poker_escrow;
     let clock = Clock::get()?;
@@ -55,22 +97,46 @@ pub fn handler(
     poker_escrow.current_players = 0;
     poker_escrow.status = GameStatus::Pending;
     poker_escrow.game_type = game_type.clone();
+    poker_escrow.fee_bps = fee_bps;
     poker_escrow.hand_identifier = hand_identifier.clone();
     poker_escrow.players = Vec::new();
     poker_escrow.hand_results = Vec::new();
     poker_escrow.created_at = clock.unix_timestamp;
     poker_escrow.started_at = None;
     poker_escrow.completed_at = None;
+    poker_escrow.last_activity_at = clock.unix_timestamp;
+    poker_escrow.idle_timeout = PokerEscrow::DEFAULT_IDLE_TIMEOUT;
+    poker_escrow.settlement_window = 0;
+    poker_escrow.dispute_bond = 0;
+    poker_escrow.attestation_threshold = 0;
+    poker_escrow.payout_signers = Vec::new();
+    poker_escrow.payout_threshold = 0;
+    poker_escrow.large_payout_amount = 0;
+    poker_escrow.paid_out = 0;
+    poker_escrow.season = config.current_season;
+    poker_escrow.access = rules.access;
+    poker_escrow.token_gate = rules.token_gate;
+    poker_escrow.required_attester = rules.required_attester;
+    poker_escrow.required_attestation_flags = attestation_flags;
+    poker_escrow.bounty_bps = if game_type == GameType::KnockoutTournament {
+        PokerEscrow::DEFAULT_BOUNTY_BPS
+    } else {
+        0
+    };
+    poker_escrow.progressive_bounty = false;
     poker_escrow.dealer_position = 0;
     poker_escrow.hand_number = 0;
     poker_escrow.total_hands_played = 0;
+    poker_escrow.dealer_seed = [0; 32];
+    poker_escrow.dealer_seed_slot = 0;
     poker_escrow.bump = ctx.bumps.poker_escrow;
 
     msg!(
-        "Poker game created: ID={}, type={:?}, buy_in={} lamports, players={}-{}, hand_id={}", 
+        "Poker game created: ID={}, type={:?}, buy_in={} lamports, fee={} bps, players={}-{}, hand_id={}", 
         game_id, 
         game_type,
         buy_in, 
+        fee_bps,
         min_players, 
         max_players,
         hand_identifier
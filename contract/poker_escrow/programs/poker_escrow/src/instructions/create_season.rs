@@ -0,0 +1,69 @@
+// programs/poker_escrow/src/instructions/create_season.rs
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Season, PointsFormula};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct CreateSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PokerError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Season::MAX_SIZE,
+        seeds = [Season::SEED_PREFIX, season_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateSeason>,
+    season_id: u32,
+    starts_at: i64,
+    ends_at: i64,
+    points: PointsFormula,
+    rake_share_bps: u16,
+    prize_bps: Vec<u16>,
+) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+
+    season.season_id = season_id;
+    season.starts_at = starts_at;
+    season.ends_at = ends_at;
+    season.points = points;
+    season.rake_share_bps = rake_share_bps;
+    season.prize_bps = prize_bps;
+    season.prize_pool = 0;
+    season.entries = 0;
+    season.standings = Vec::new();
+    season.closed = false;
+    season.bump = ctx.bumps.season;
+    season.validate()?;
+
+    // Games created from now on score points for this season
+    ctx.accounts.config.current_season = Some(season.key());
+
+    msg!(
+        "Season {} created: {} to {}, rake share {} bps, {} prizes",
+        season_id,
+        starts_at,
+        ends_at,
+        season.rake_share_bps,
+        season.prize_bps.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// programs/poker_escrow/src/instructions/create_session.rs
+use anchor_lang::prelude::*;
+use crate::state::SessionToken;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        space = SessionToken::MAX_SIZE,
+        seeds = [SessionToken::SEED_PREFIX, player.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    permissions: u8,
+    spend_limit: u64,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        permissions != 0 && permissions & !SessionToken::ALL == 0,
+        PokerError::InvalidSession
+    );
+    require!(
+        expires_at > now && expires_at <= now + SessionToken::MAX_DURATION,
+        PokerError::InvalidSession
+    );
+
+    let session_token = &mut ctx.accounts.session_token;
+    session_token.player = ctx.accounts.player.key();
+    session_token.session_key = session_key;
+    session_token.permissions = permissions;
+    session_token.spend_limit = spend_limit;
+    session_token.spent = 0;
+    session_token.created_at = now;
+    session_token.expires_at = expires_at;
+    session_token.bump = ctx.bumps.session_token;
+
+    msg!(
+        "Player {} authorized session key {} (permissions: {:#05b}, spend limit: {} lamports) until {}",
+        session_token.player,
+        session_key,
+        permissions,
+        spend_limit,
+        expires_at
+    );
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+// programs/poker_escrow/src/instructions/deposit.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::Bankroll;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = Bankroll::MAX_SIZE,
+        seeds = [Bankroll::SEED_PREFIX, player.key().as_ref()],
+        bump
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    require!(amount > 0, PokerError::InvalidAmount);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: ctx.accounts.bankroll.to_account_info(),
+            }
+        ),
+        amount,
+    )?;
+
+    let bankroll = &mut ctx.accounts.bankroll;
+    if bankroll.player == Pubkey::default() {
+        bankroll.player = ctx.accounts.player.key();
+        bankroll.created_at = Clock::get()?.unix_timestamp;
+        bankroll.bump = ctx.bumps.bankroll;
+    }
+    bankroll.total_deposited = bankroll.total_deposited
+        .checked_add(amount)
+        .ok_or(PokerError::MathOverflow)?;
+
+    msg!(
+        "Player {} deposited {} lamports, bankroll: {}",
+        bankroll.player,
+        amount,
+        Bankroll::available(&bankroll.to_account_info())?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+// programs/poker_escrow/src/instructions/dispute_settlement.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::{PokerEscrow, Settlement, SettlementStatus};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct DisputeSettlement<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// CHECK: Game server account (verified in escrow)
+    pub game_server: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        mut,
+        seeds = [
+            Settlement::SEED_PREFIX,
+            poker_escrow.key().as_ref(),
+            settlement.hand_index.to_le_bytes().as_ref()
+        ],
+        bump = settlement.bump,
+        has_one = poker_escrow,
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DisputeSettlement>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_key = ctx.accounts.player.key();
+    let dispute_bond = ctx.accounts.poker_escrow.dispute_bond;
+
+    // Only seated players can dispute, and only while the window is open
+    require!(ctx.accounts.poker_escrow.has_player(&player_key), PokerError::PlayerNotInGame);
+    require!(
+        ctx.accounts.settlement.status == SettlementStatus::Proposed,
+        PokerError::SettlementDisputed
+    );
+    require!(
+        clock.unix_timestamp <= ctx.accounts.settlement.challenge_deadline,
+        PokerError::ChallengeWindowClosed
+    );
+
+    // Stake the dispute bond in the settlement account
+    if dispute_bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.settlement.to_account_info(),
+                }
+            ),
+            dispute_bond,
+        )?;
+    }
+
+    let settlement = &mut ctx.accounts.settlement;
+    settlement.status = SettlementStatus::Disputed;
+    settlement.disputed_by = Some(player_key);
+    settlement.dispute_bond = dispute_bond;
+
+    msg!(
+        "Player {} disputed settlement of hand {} in poker game {} with {} lamports bond",
+        player_key,
+        settlement.hand_index,
+        ctx.accounts.poker_escrow.game_id,
+        dispute_bond
+    );
+
+    Ok(())
+}
@@ -1,12 +1,22 @@
 // programs/poker_escrow/src/instructions/distribute_pot.rs
 use anchor_lang::prelude::*;
-use crate::state::{PokerEscrow, GameStatus, GameType};
+use crate::state::{
+    PokerEscrow, GameStatus, ProgramConfig, GameServerProfile, PlayerProfile, Season, SeasonEntry,
+};
 use crate::errors::PokerError;
+use crate::utils::{pay_winner, finish_hand, require_payout_cosigners, live_season, credit_season_points};
 
 #[derive(Accounts)]
 pub struct DistributePot<'info> {
     #[account(mut)]
-    pub game_server: Signer<'info>,
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_perform(&operator.key(), GameServerProfile::DELEGATE_DISTRIBUTE_POT) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
 
     #[account(
         mut,
@@ -20,16 +30,53 @@ pub struct DistributePot<'info> {
     )]
     pub poker_escrow: Account<'info, PokerEscrow>,
 
-    /// Winner who will receive the payout
+    /// Winner of the hand
     /// CHECK: This account is verified to be a player in the game
-    #[account(mut)]
     pub winner: UncheckedAccount<'info>,
 
+    /// CHECK: Where the winner is paid, their wallet or their bankroll as
+    /// recorded when they joined. Verified against the escrow.
+    #[account(mut)]
+    pub payout_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, winner.key().as_ref()],
+        bump = winner_profile.bump,
+    )]
+    pub winner_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Protocol treasury, verified against the program config
+    #[account(mut, address = config.treasury @ PokerError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Game server owner, receives rent when the escrow closes
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
+    /// Season the game scores for, required when the game has one
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+
+    /// Winner's entry in that season, if they signed up
+    #[account(mut)]
+    pub season_entry: Option<Account<'info, SeasonEntry>>,
+
     pub system_program: Program<'info, System>,
+    // remaining_accounts: every player's rating account, in the order they
+    // joined, when this payout completes a SingleHand or Tournament game,
+    // then co-signing payout signers when the amount requires them
 }
 
-pub fn handler(
-    ctx: Context<DistributePot>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributePot<'info>>,
     amount: u64,
     hand_rank: u8,
     hand_description: String,
@@ -37,64 +84,62 @@ pub fn handler(
     let winner = ctx.accounts.winner.key();
 
     // Extract values we need before borrowing mutably
-    let game_id = ctx.accounts.poker_escrow.game_id;
     let current_status = ctx.accounts.poker_escrow.status.clone();
-    let current_pot = ctx.accounts.poker_escrow.total_pot;
-    let has_winner = ctx.accounts.poker_escrow.has_player(&winner);
-    let game_type = ctx.accounts.poker_escrow.game_type.clone();
+    let settlement_window = ctx.accounts.poker_escrow.settlement_window;
 
     // Validate game state
     require!(current_status == GameStatus::Active, PokerError::GameNotActive);
-    require!(has_winner, PokerError::PlayerNotInGame);
-    require!(amount <= current_pot, PokerError::PayoutMismatch);
+    require!(settlement_window == 0, PokerError::SettlementWindowRequired);
     require!(hand_rank <= 9, PokerError::InvalidHandResult); // 0-9 for poker hands
     require!(!hand_description.is_empty(), PokerError::InvalidHandResult);
 
-    // Transfer SOL from escrow to winner
-    **ctx.accounts.poker_escrow.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **ctx.accounts.winner.to_account_info().try_borrow_mut_lamports()? += amount;
+    let accounts = ctx.remaining_accounts;
+    let rated_players = if ctx.accounts.poker_escrow.completes_rated_game(amount) {
+        ctx.accounts.poker_escrow.players.len()
+    } else {
+        0
+    };
+    let (ratings, cosigners) = accounts.split_at(rated_players.min(accounts.len()));
+    require_payout_cosigners(
+        &ctx.accounts.poker_escrow,
+        &ctx.accounts.operator.key(),
+        cosigners,
+        amount,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut season = live_season(&ctx.accounts.poker_escrow, ctx.accounts.season.as_mut(), now)?;
+
+    // Transfer SOL from escrow to winner, treasury and season prize pool
+    let (winner_amount, fee) = pay_winner(
+        &mut ctx.accounts.poker_escrow,
+        &winner,
+        &ctx.accounts.payout_account.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        season.as_deref_mut(),
+        amount,
+    )?;
 
-    // Update pot and add hand result
-    let poker_escrow = &mut ctx.accounts.poker_escrow;
-    poker_escrow.total_pot -= amount;
-    
     // Record the hand result for transparency
-    poker_escrow.add_hand_result(winner, hand_rank, hand_description.clone(), amount)?;
-
-    // Always complete the hand and rotate dealer for multi-hand games
-    match game_type {
-        GameType::SingleHand => {
-            // Single hand games complete and close account after one payout
-            poker_escrow.complete_hand()?;
-            
-            // Close account and return rent to game server
-            let dest_starting_lamports = ctx.accounts.game_server.lamports();
-            **ctx.accounts.game_server.lamports.borrow_mut() = dest_starting_lamports
-                .checked_add(ctx.accounts.poker_escrow.to_account_info().lamports())
-                .unwrap();
-            **ctx.accounts.poker_escrow.to_account_info().lamports.borrow_mut() = 0;
-            
-            msg!("Single-hand poker game {} completed and closed", game_id);
-        },
-        GameType::Tournament | GameType::CashGame => {
-            // For multi-hand games, always complete hand and rotate dealer after each payout
-            poker_escrow.complete_hand()?;
-            poker_escrow.rotate_dealer()?;
-            
-            // If pot is empty, mark game as completed
-            if poker_escrow.total_pot == 0 {
-                poker_escrow.status = GameStatus::Completed;
-                msg!("Multi-hand poker game {} completed - all funds distributed", game_id);
-            } else {
-                msg!("Hand completed in poker game {}, ready for next hand", game_id);
-            }
-        }
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+    poker_escrow.touch(now);
+    poker_escrow.add_hand_result(winner, hand_rank, hand_description.clone(), winner_amount)?;
+    ctx.accounts.winner_profile.record_win(amount, winner_amount)?;
+
+    // Credit season points if the winner signed up for the season
+    if let (Some(season), Some(entry)) = (season, ctx.accounts.season_entry.as_mut()) {
+        let ends_tournament = poker_escrow.game_type.is_tournament() && poker_escrow.total_pot == 0;
+        credit_season_points(season, entry, &winner, winner_amount, ends_tournament)?;
     }
 
+    // Always complete the hand; single hands close, multi-hand games rotate dealer
+    finish_hand(poker_escrow, &ctx.accounts.server_authority.to_account_info(), ratings)?;
+
     msg!(
-        "Paid {} lamports to winner {} with {} (rank: {})", 
-        amount, 
+        "Paid {} lamports to winner {} (fee: {}) with {} (rank: {})", 
+        winner_amount, 
         winner, 
+        fee,
         hand_description,
         hand_rank
     );
@@ -0,0 +1,142 @@
+// programs/poker_escrow/src/instructions/distribute_pot_attested.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{PokerEscrow, GameStatus, Payout, ProgramConfig, Settlement, GameServerProfile, Season};
+use crate::errors::PokerError;
+use crate::utils::{WINNER_ACCOUNTS, live_season, ed25519_signers, pay_winners, finish_hand, require_payout_cosigners};
+
+#[derive(Accounts)]
+pub struct DistributePotAttested<'info> {
+    /// Anyone can submit an attested settlement (a player, the server, a relayer)
+    pub submitter: Signer<'info>,
+
+    /// Game server counts as one attestation when its owner or operator
+    /// signs the payout list or submits the transaction. Delegate hot keys
+    /// don't count, so they can't stand in for the server's own signature.
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    /// CHECK: Game server owner, receives rent on close
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Protocol treasury, verified against the program config
+    #[account(mut, address = config.treasury @ PokerError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the Ed25519 precompile instructions
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Season the game scores for, required when the game has one
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (winner payout account, player profile, season entry or
+    // program id) triples, writable, in payout order,
+    // then every player's rating account, in the order they joined, when
+    // this completes a rated game, then co-signing payout signers when the
+    // amount requires them
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributePotAttested<'info>>,
+    payouts: Vec<Payout>,
+    hand_rank: u8,
+    hand_description: String,
+) -> Result<()> {
+    let poker_escrow = &ctx.accounts.poker_escrow;
+    let threshold = poker_escrow.attestation_threshold;
+
+    // Validate game state
+    require!(poker_escrow.status == GameStatus::Active, PokerError::GameNotActive);
+    require!(threshold > 0, PokerError::AttestationDisabled);
+    require!(poker_escrow.settlement_window == 0, PokerError::SettlementWindowRequired);
+    require!(hand_rank <= 9, PokerError::InvalidHandResult); // 0-9 for poker hands
+    require!(!hand_description.is_empty(), PokerError::InvalidHandResult);
+    require!(hand_description.len() <= 32, PokerError::InvalidHandResult);
+    let total = Settlement::validate_payouts(poker_escrow, &payouts)?;
+
+    // Count distinct seated players (and the server) who signed this exact payout list
+    let message = Settlement::attestation_message(
+        &poker_escrow.key(),
+        poker_escrow.total_hands_played,
+        &payouts,
+        hand_rank,
+        &hand_description,
+    );
+    let signers = ed25519_signers(&ctx.accounts.instructions.to_account_info(), &message)?;
+    let player_attestations = signers
+        .iter()
+        .filter(|signer| poker_escrow.has_player(signer))
+        .count();
+    let game_server = &ctx.accounts.game_server;
+    let server_attested = game_server.can_operate(&ctx.accounts.submitter.key()) ||
+        signers.iter().any(|signer| game_server.can_operate(signer));
+    let attestations = player_attestations + server_attested as usize;
+
+    require!(attestations >= threshold as usize, PokerError::InsufficientAttestations);
+
+    // Attestations don't stand in for the payout signers: seated players
+    // may all be the server's own accounts
+    let accounts = ctx.remaining_accounts;
+    let (winners, accounts) = accounts.split_at((payouts.len() * WINNER_ACCOUNTS).min(accounts.len()));
+    let rated_players = if poker_escrow.completes_rated_game(total) {
+        poker_escrow.players.len()
+    } else {
+        0
+    };
+    let (ratings, cosigners) = accounts.split_at(rated_players.min(accounts.len()));
+    require_payout_cosigners(poker_escrow, &ctx.accounts.submitter.key(), cosigners, total)?;
+
+    let game_id = poker_escrow.game_id;
+
+    let now = Clock::get()?.unix_timestamp;
+    let season = live_season(&ctx.accounts.poker_escrow, ctx.accounts.season.as_mut(), now)?;
+
+    pay_winners(
+        &mut ctx.accounts.poker_escrow,
+        &payouts,
+        winners,
+        &ctx.accounts.treasury.to_account_info(),
+        season,
+        hand_rank,
+        &hand_description,
+    )?;
+
+    ctx.accounts.poker_escrow.touch(now);
+    finish_hand(&mut ctx.accounts.poker_escrow, &ctx.accounts.server_authority.to_account_info(), ratings)?;
+
+    msg!(
+        "Attested settlement paid {} lamports in poker game {} ({} player attestations, server: {})",
+        total,
+        game_id,
+        player_attestations,
+        server_attested
+    );
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// programs/poker_escrow/src/instructions/eliminate_player.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, GameType, SeatStatus, ProgramConfig, GameServerProfile, Season};
+use crate::errors::PokerError;
+use crate::utils::{pay_winner, require_payout_cosigners, live_season};
+
+#[derive(Accounts)]
+pub struct EliminatePlayer<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    /// CHECK: Where the eliminator is paid, their wallet or the account that
+    /// paid their buy-in. Verified against the escrow.
+    #[account(mut)]
+    pub payout_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Protocol treasury, verified against the program config
+    #[account(mut, address = config.treasury @ PokerError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Season the game scores for, required when the game has one
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+    // remaining_accounts: co-signing payout signers, when the bounty
+    // requires them
+}
+
+/// Knock `player` out of a knockout tournament, paying their bounty (half of
+/// it if progressive) to `eliminator`. The rest stays in the prize pool.
+pub fn handler(
+    ctx: Context<EliminatePlayer>,
+    player: Pubkey,
+    eliminator: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let season = live_season(&ctx.accounts.poker_escrow, ctx.accounts.season.as_mut(), now)?;
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    require!(poker_escrow.status == GameStatus::Active, PokerError::GameNotActive);
+    require!(
+        poker_escrow.game_type == GameType::KnockoutTournament,
+        PokerError::InvalidGameType
+    );
+    let eliminator_status = poker_escrow.players
+        .iter()
+        .find(|p| p.player == eliminator)
+        .map(|p| p.status)
+        .ok_or(PokerError::PlayerNotInGame)?;
+    require!(
+        eliminator != player && eliminator_status != SeatStatus::Eliminated,
+        PokerError::InvalidEliminator
+    );
+
+    let released = poker_escrow.knock_out(&player)?;
+    let bounty = if poker_escrow.progressive_bounty {
+        released / 2
+    } else {
+        released
+    };
+
+    if bounty > 0 {
+        require_payout_cosigners(
+            poker_escrow,
+            &ctx.accounts.operator.key(),
+            ctx.remaining_accounts,
+            bounty,
+        )?;
+        let (received, fee) = pay_winner(
+            poker_escrow,
+            &eliminator,
+            &ctx.accounts.payout_account.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            season,
+            bounty,
+        )?;
+        msg!("Paid bounty of {} lamports to {} (fee: {})", received, eliminator, fee);
+    }
+    poker_escrow.touch(now);
+
+    msg!(
+        "Player {} eliminated from poker game {} by {}, prize pool: {} lamports",
+        player,
+        poker_escrow.game_id,
+        eliminator,
+        poker_escrow.prize_pool()
+    );
+
+    Ok(())
+}
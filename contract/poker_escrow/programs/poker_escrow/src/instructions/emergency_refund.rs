@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
-use crate::state::{PokerEscrow, GameStatus};
+use crate::state::{PokerEscrow, GameStatus, PlayerProfile, SessionToken};
 use crate::errors::PokerError;
+use crate::utils::{transfer_lamports, authorize_player};
 
 #[derive(Accounts)]
 pub struct EmergencyRefund<'info> {
+    /// The player, or a session key they authorized
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Player leaving. Must sign, or have authorized `authority`
+    /// through `session_token`.
+    pub player: UncheckedAccount<'info>,
+    
+    /// Session authorizing `authority`, when the player isn't signing
     #[account(mut)]
-    pub player: Signer<'info>,
+    pub session_token: Option<Account<'info, SessionToken>>,
     
     /// CHECK: Game server account (verified in escrow)
     pub game_server: UncheckedAccount<'info>,
@@ -22,6 +31,20 @@ pub struct EmergencyRefund<'info> {
     )]
     pub poker_escrow: Account<'info, PokerEscrow>,
     
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    /// CHECK: Player's wallet or bankroll, as recorded when they joined
+    #[account(
+        mut,
+        address = poker_escrow.payout_address(&player.key()) @ PokerError::InvalidRefundAccount
+    )]
+    pub payout_account: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -30,43 +53,39 @@ pub fn handler(ctx: Context<EmergencyRefund>) -> Result<()> {
     let player_key = ctx.accounts.player.key();
     
     // Extract values we need before borrowing mutably
-    let current_status = ctx.accounts.poker_escrow.status.clone();
-    let created_at = ctx.accounts.poker_escrow.created_at;
     let has_player = ctx.accounts.poker_escrow.has_player(&player_key);
     
     // Validate player is in the game
     require!(has_player, PokerError::PlayerNotInGame);
+    authorize_player(
+        &ctx.accounts.player,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_token.as_mut(),
+        SessionToken::LEAVE,
+        0,
+    )?;
     
     // Emergency refund conditions:
     // 1. Game is pending and 24 hours have passed, OR
-    // 2. Game is marked as abandoned
-    let refund_timeout = 24 * 60 * 60; // 24 hours in seconds
-    let can_emergency_refund = match current_status {
-        GameStatus::Pending => {
-            clock.unix_timestamp - created_at > refund_timeout
-        },
-        GameStatus::Abandoned => true,
-        _ => false,
-    };
+    // 2. Game is active but the server has been idle past its timeout, OR
+    // 3. Game is marked as abandoned
+    let can_emergency_refund = ctx.accounts.poker_escrow.is_refundable(clock.unix_timestamp);
     
     require!(can_emergency_refund, PokerError::RefundTimeoutNotReached);
 
-    // Find player's deposit amount
-    let refund_amount = ctx.accounts.poker_escrow.players
-        .iter()
-        .find(|p| p.player == player_key)
-        .ok_or(PokerError::PlayerNotInGame)?
-        .amount;
+    // Once refunds start the game can no longer be joined or played
+    ctx.accounts.poker_escrow.status = GameStatus::Abandoned;
 
-    // Transfer SOL from escrow back to player
-    **ctx.accounts.poker_escrow.to_account_info().try_borrow_mut_lamports()? -= refund_amount;
-    **ctx.accounts.player.to_account_info().try_borrow_mut_lamports()? += refund_amount;
+    // Remove player from game and take back their share of the pot
+    let refund_amount = ctx.accounts.poker_escrow.remove_player(&player_key)?;
 
-    // Remove player from game
-    let poker_escrow = &mut ctx.accounts.poker_escrow;
-    poker_escrow.players.retain(|p| p.player != player_key);
-    poker_escrow.current_players -= 1;
-    poker_escrow.total_pot -= refund_amount;
+    // Transfer SOL from escrow back to player
+    transfer_lamports(
+        &ctx.accounts.poker_escrow.to_account_info(),
+        &ctx.accounts.payout_account.to_account_info(),
+        refund_amount,
+    )?;
+    ctx.accounts.player_profile.record_refund(refund_amount)?;
 
     msg!("Emergency refund: {} lamports returned to player {}", 
          refund_amount, player_key);
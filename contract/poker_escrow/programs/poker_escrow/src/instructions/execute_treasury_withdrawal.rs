@@ -0,0 +1,67 @@
+// programs/poker_escrow/src/instructions/execute_treasury_withdrawal.rs
+use anchor_lang::prelude::*;
+use crate::state::{Treasury, Bankroll, WithdrawalRequest, ProgramConfig};
+use crate::errors::PokerError;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct ExecuteTreasuryWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        close = requested_by,
+        seeds = [WithdrawalRequest::SEED_PREFIX, treasury.key().as_ref()],
+        bump = withdrawal_request.bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    /// CHECK: Destination fixed when the withdrawal was requested
+    #[account(mut, address = withdrawal_request.destination @ PokerError::InvalidTreasury)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// CHECK: Paid the request's rent, gets it back
+    #[account(mut, address = withdrawal_request.requested_by)]
+    pub requested_by: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler(ctx: Context<ExecuteTreasuryWithdrawal>) -> Result<()> {
+    let request = &ctx.accounts.withdrawal_request;
+    require!(
+        Clock::get()?.unix_timestamp >= request.unlocks_at,
+        PokerError::WithdrawalLocked
+    );
+
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    require!(
+        Bankroll::available(&treasury_info)? >= request.amount,
+        PokerError::InsufficientBalance
+    );
+    transfer_lamports(&treasury_info, &ctx.accounts.destination.to_account_info(), request.amount)?;
+
+    let amount = request.amount;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_withdrawn = treasury.total_withdrawn
+        .checked_add(amount)
+        .ok_or(PokerError::MathOverflow)?;
+
+    msg!(
+        "Treasury paid {} lamports to {}",
+        amount,
+        ctx.accounts.destination.key()
+    );
+
+    Ok(())
+}
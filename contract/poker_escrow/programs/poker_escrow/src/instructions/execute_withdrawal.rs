@@ -0,0 +1,69 @@
+// programs/poker_escrow/src/instructions/execute_withdrawal.rs
+use anchor_lang::prelude::*;
+use crate::state::{Bankroll, WithdrawalRequest, ProgramConfig};
+use crate::errors::PokerError;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    /// CHECK: Player's wallet, the only destination a bankroll pays out to
+    #[account(mut, address = bankroll.player @ PokerError::InvalidBankroll)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Bankroll::SEED_PREFIX, bankroll.player.as_ref()],
+        bump = bankroll.bump,
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+
+    #[account(
+        mut,
+        close = requested_by,
+        seeds = [WithdrawalRequest::SEED_PREFIX, bankroll.key().as_ref()],
+        bump = withdrawal_request.bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    /// CHECK: Paid the request's rent, gets it back
+    #[account(mut, address = withdrawal_request.requested_by)]
+    pub requested_by: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+    let request = &ctx.accounts.withdrawal_request;
+    require!(
+        Clock::get()?.unix_timestamp >= request.unlocks_at,
+        PokerError::WithdrawalLocked
+    );
+
+    // Funds may have gone into games since the request
+    let bankroll_info = ctx.accounts.bankroll.to_account_info();
+    require!(
+        Bankroll::available(&bankroll_info)? >= request.amount,
+        PokerError::InsufficientBalance
+    );
+    transfer_lamports(&bankroll_info, &ctx.accounts.player.to_account_info(), request.amount)?;
+
+    let amount = request.amount;
+    let bankroll = &mut ctx.accounts.bankroll;
+    bankroll.total_withdrawn = bankroll.total_withdrawn
+        .checked_add(amount)
+        .ok_or(PokerError::MathOverflow)?;
+
+    msg!(
+        "Player {} withdrew {} lamports, bankroll: {}",
+        bankroll.player,
+        amount,
+        Bankroll::available(&bankroll_info)?
+    );
+
+    Ok(())
+}
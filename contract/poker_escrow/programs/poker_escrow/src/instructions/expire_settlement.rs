@@ -0,0 +1,87 @@
+// programs/poker_escrow/src/instructions/expire_settlement.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, GameServerProfile, Settlement};
+use crate::errors::PokerError;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct ExpireSettlement<'info> {
+    /// Anyone can expire a settlement nobody finalized or resolved
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    /// CHECK: Game server owner, receives rent on close
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        mut,
+        close = server_authority,
+        seeds = [
+            Settlement::SEED_PREFIX,
+            poker_escrow.key().as_ref(),
+            settlement.hand_index.to_le_bytes().as_ref()
+        ],
+        bump = settlement.bump,
+        has_one = poker_escrow,
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    /// CHECK: Player who disputed, gets their bond back. Any account when
+    /// the settlement was not disputed.
+    #[account(
+        mut,
+        constraint = settlement.disputed_by.is_none_or(|player| player == disputer.key()) @ PokerError::SettlementNotDisputed,
+    )]
+    pub disputer: UncheckedAccount<'info>,
+}
+
+/// Abandon a game whose settlement has sat unpaid for the idle timeout past
+/// its challenge window, so players can take refunds instead of waiting on
+/// a payout that may never succeed.
+pub fn handler(ctx: Context<ExpireSettlement>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let settlement = &ctx.accounts.settlement;
+    let idle_timeout = ctx.accounts.poker_escrow.idle_timeout;
+
+    require!(ctx.accounts.poker_escrow.status == GameStatus::Settling, PokerError::GameNotActive);
+    require!(settlement.is_expired(idle_timeout, now), PokerError::SettlementNotExpired);
+
+    // Nobody ruled on the dispute, so the bond goes back
+    if settlement.dispute_bond > 0 {
+        transfer_lamports(
+            &settlement.to_account_info(),
+            &ctx.accounts.disputer.to_account_info(),
+            settlement.dispute_bond,
+        )?;
+    }
+
+    let hand_index = settlement.hand_index;
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+    poker_escrow.status = GameStatus::Abandoned;
+
+    msg!(
+        "Settlement of hand {} in poker game {} expired, deposits can be refunded",
+        hand_index,
+        poker_escrow.game_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+// programs/poker_escrow/src/instructions/finalize_channel.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, Channel, ProgramConfig, PlayerProfile, Season};
+use crate::errors::PokerError;
+use crate::utils::{WINNER_ACCOUNTS, transfer_lamports, collect_fee, live_season, season_entry, credit_season_points, require_payout_cosigners};
+
+#[derive(Accounts)]
+pub struct FinalizeChannel<'info> {
+    /// Anyone can finalize once the challenge window passes
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Game server account (verified in escrow)
+    pub game_server: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [Channel::SEED_PREFIX, poker_escrow.key().as_ref()],
+        bump = channel.bump,
+        has_one = poker_escrow,
+        has_one = rent_payer,
+    )]
+    pub channel: Account<'info, Channel>,
+
+    /// CHECK: Original submitter of the channel state, receives its rent
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Protocol treasury, verified against the program config
+    #[account(mut, address = config.treasury @ PokerError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Season the game scores for, required when the game has one
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (player payout account, player profile, season entry
+    // or program id) triples, writable, in channel stack order, then
+    // co-signing payout signers when the pot requires them
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeChannel<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let channel = &ctx.accounts.channel;
+
+    require!(ctx.accounts.poker_escrow.status == GameStatus::Settling, PokerError::GameNotActive);
+    require!(
+        clock.unix_timestamp > channel.challenge_deadline,
+        PokerError::ChallengeWindowOpen
+    );
+    require!(
+        ctx.remaining_accounts.len() >= channel.stacks.len() * WINNER_ACCOUNTS,
+        PokerError::PayoutAccountMismatch
+    );
+    let (players, cosigners) = ctx.remaining_accounts.split_at(channel.stacks.len() * WINNER_ACCOUNTS);
+
+    // The pot must still match the channel state being paid out, all of
+    // which leaves the escrow
+    Channel::validate_stacks(&ctx.accounts.poker_escrow, &channel.stacks)?;
+    let total_pot = ctx.accounts.poker_escrow.total_pot;
+    require_payout_cosigners(&ctx.accounts.poker_escrow, &ctx.accounts.cranker.key(), cosigners, total_pot)?;
+
+    let fee_bps = ctx.accounts.poker_escrow.fee_bps;
+    let escrow_info = ctx.accounts.poker_escrow.to_account_info();
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let mut season = live_season(&ctx.accounts.poker_escrow, ctx.accounts.season.as_mut(), clock.unix_timestamp)?;
+
+    // Pay every stack out, taking the protocol fee only on net winnings
+    for (update, accounts) in channel.stacks.iter().zip(players.chunks(WINNER_ACCOUNTS)) {
+        let (wallet, profile_info) = (&accounts[0], &accounts[1]);
+        require_keys_eq!(
+            ctx.accounts.poker_escrow.payout_address(&update.player),
+            *wallet.key,
+            PokerError::PayoutAccountMismatch
+        );
+        require!(wallet.is_writable, PokerError::PayoutAccountMismatch);
+
+        let buy_in = ctx.accounts.poker_escrow.players
+            .iter()
+            .find(|p| p.player == update.player)
+            .ok_or(PokerError::PlayerNotInGame)?
+            .amount;
+        let net_winnings = update.stack.saturating_sub(buy_in);
+        let fee = ProgramConfig::fee_for(net_winnings, fee_bps)?;
+
+        transfer_lamports(&escrow_info, wallet, update.stack - fee)?;
+        collect_fee(&escrow_info, &treasury_info, season.as_deref_mut(), fee)?;
+
+        // Only what a player won on top of their buy-in counts as winnings
+        let mut profile: Account<PlayerProfile> = Account::try_from(profile_info)?;
+        require_keys_eq!(profile.player, update.player, PokerError::InvalidPlayerProfile);
+        if net_winnings > 0 {
+            profile.record_win(net_winnings, net_winnings - fee)?;
+            profile.exit(&crate::ID)?;
+
+            if let (Some(season), Some(mut entry)) = (season.as_mut(), season_entry(&accounts[2])?) {
+                credit_season_points(season, &mut entry, &update.player, net_winnings - fee, false)?;
+                entry.exit(&crate::ID)?;
+            }
+        }
+
+        msg!("Channel paid {} lamports to player {} (fee: {})", update.stack - fee, update.player, fee);
+    }
+
+    let nonce = channel.nonce;
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+    poker_escrow.total_pot = 0;
+    poker_escrow.paid_out = poker_escrow.paid_out
+        .checked_add(total_pot)
+        .ok_or(PokerError::MathOverflow)?;
+    poker_escrow.status = GameStatus::Completed;
+    poker_escrow.completed_at = Some(clock.unix_timestamp);
+
+    msg!("Channel for poker game {} settled at nonce {}", poker_escrow.game_id, nonce);
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// programs/poker_escrow/src/instructions/finalize_settlement.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, ProgramConfig, Settlement, SettlementStatus, GameServerProfile, Season};
+use crate::errors::PokerError;
+use crate::utils::{WINNER_ACCOUNTS, live_season, pay_winners, finish_hand};
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    /// Anyone can finalize once the challenge window passes undisputed
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    /// CHECK: Game server owner, receives rent on close
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        mut,
+        close = server_authority,
+        seeds = [
+            Settlement::SEED_PREFIX,
+            poker_escrow.key().as_ref(),
+            settlement.hand_index.to_le_bytes().as_ref()
+        ],
+        bump = settlement.bump,
+        has_one = poker_escrow,
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Protocol treasury, verified against the program config
+    #[account(mut, address = config.treasury @ PokerError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Season the game scores for, required when the game has one
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (winner payout account, player profile, season entry or
+    // program id) triples, writable, in settlement payout order,
+    // then every player's rating account, in the order they joined, when
+    // this completes a rated game
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeSettlement<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let settlement = &ctx.accounts.settlement;
+
+    require!(ctx.accounts.poker_escrow.status == GameStatus::Settling, PokerError::GameNotActive);
+    require!(settlement.status == SettlementStatus::Proposed, PokerError::SettlementDisputed);
+    require!(
+        clock.unix_timestamp > settlement.challenge_deadline,
+        PokerError::ChallengeWindowOpen
+    );
+
+    let game_id = ctx.accounts.poker_escrow.game_id;
+    let hand_index = settlement.hand_index;
+
+    // Play resumes once the hand is paid
+    ctx.accounts.poker_escrow.status = GameStatus::Active;
+
+    let season = live_season(&ctx.accounts.poker_escrow, ctx.accounts.season.as_mut(), clock.unix_timestamp)?;
+    let accounts = ctx.remaining_accounts;
+    let (winners, ratings) = accounts.split_at((settlement.payouts.len() * WINNER_ACCOUNTS).min(accounts.len()));
+
+    pay_winners(
+        &mut ctx.accounts.poker_escrow,
+        &settlement.payouts,
+        winners,
+        &ctx.accounts.treasury.to_account_info(),
+        season,
+        settlement.hand_rank,
+        &settlement.hand_description,
+    )?;
+
+    finish_hand(&mut ctx.accounts.poker_escrow, &ctx.accounts.server_authority.to_account_info(), ratings)?;
+
+    msg!("Settlement of hand {} in poker game {} finalized", hand_index, game_id);
+
+    Ok(())
+}
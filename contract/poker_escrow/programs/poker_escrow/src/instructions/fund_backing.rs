@@ -0,0 +1,59 @@
+// programs/poker_escrow/src/instructions/fund_backing.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::Backing;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct FundBacking<'info> {
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Backing::SEED_PREFIX, backing.poker_escrow.as_ref(), backing.player.as_ref()],
+        bump = backing.bump,
+        constraint = !backing.joined @ PokerError::InvalidBacking,
+    )]
+    pub backing: Account<'info, Backing>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundBacking>) -> Result<()> {
+    let backer_key = ctx.accounts.backer.key();
+    let amount = {
+        let backer = ctx.accounts.backing.backers
+            .iter()
+            .find(|b| b.terms.backer == backer_key)
+            .ok_or(PokerError::InvalidBacking)?;
+        require!(!backer.funded, PokerError::InvalidBacking);
+        backer.terms.amount
+    };
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.backer.to_account_info(),
+                to: ctx.accounts.backing.to_account_info(),
+            }
+        ),
+        amount,
+    )?;
+
+    let backing = &mut ctx.accounts.backing;
+    if let Some(backer) = backing.backers.iter_mut().find(|b| b.terms.backer == backer_key) {
+        backer.funded = true;
+    }
+
+    msg!(
+        "Backer {} staked {} lamports on player {}, fully funded: {}",
+        backer_key,
+        amount,
+        backing.player,
+        backing.is_funded()
+    );
+
+    Ok(())
+}
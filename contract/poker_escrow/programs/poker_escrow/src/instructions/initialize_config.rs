@@ -0,0 +1,72 @@
+// programs/poker_escrow/src/instructions/initialize_config.rs
+use anchor_lang::prelude::*;
+use crate::program::PokerEscrow as PokerEscrowProgram;
+use crate::state::{ProgramConfig, GameType};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    /// Program upgrade authority becomes the first admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::MAX_SIZE,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ PokerError::UnauthorizedAdmin
+    )]
+    pub program: Program<'info, PokerEscrowProgram>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ PokerError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeConfig>,
+    treasury: Pubkey,
+    fee_bps: u16,
+    allowed_game_types: Vec<GameType>,
+    max_buy_in: u64,
+    min_server_bond: u64,
+    arbiter: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.admin = ctx.accounts.admin.key();
+    config.pending_admin = None;
+    config.treasury = treasury;
+    config.fee_bps = fee_bps;
+    config.allowed_game_types = allowed_game_types;
+    config.max_buy_in = max_buy_in;
+    config.min_server_bond = min_server_bond;
+    config.arbiter = arbiter;
+    config.guardian = ctx.accounts.admin.key();
+    config.paused = false;
+    config.current_season = None;
+    config.pending_change = None;
+    config.bump = ctx.bumps.config;
+    config.validate()?;
+
+    msg!(
+        "Program config initialized: admin={}, treasury={}, fee={} bps, max_buy_in={} lamports",
+        config.admin,
+        config.treasury,
+        config.fee_bps,
+        config.max_buy_in
+    );
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// programs/poker_escrow/src/instructions/initialize_treasury.rs
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Treasury};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PokerError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Treasury::MAX_SIZE,
+        seeds = [Treasury::SEED_PREFIX],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_withdrawn = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    // From now on fees accumulate in the vault
+    ctx.accounts.config.treasury = treasury.key();
+
+    msg!("Protocol fees now go to treasury vault {}", treasury.key());
+
+    Ok(())
+}
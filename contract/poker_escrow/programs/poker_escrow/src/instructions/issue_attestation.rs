@@ -0,0 +1,55 @@
+// programs/poker_escrow/src/instructions/issue_attestation.rs
+use anchor_lang::prelude::*;
+use crate::state::ComplianceAttestation;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct IssueAttestation<'info> {
+    #[account(mut)]
+    pub attester: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = attester,
+        space = ComplianceAttestation::MAX_SIZE,
+        seeds = [ComplianceAttestation::SEED_PREFIX, attester.key().as_ref(), player.as_ref()],
+        bump
+    )]
+    pub compliance_attestation: Account<'info, ComplianceAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<IssueAttestation>,
+    player: Pubkey,
+    flags: u8,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        flags != 0 && flags & !ComplianceAttestation::ALL_FLAGS == 0,
+        PokerError::InvalidComplianceAttestation
+    );
+    require!(expires_at > now, PokerError::ComplianceAttestationExpired);
+
+    let attestation = &mut ctx.accounts.compliance_attestation;
+    attestation.attester = ctx.accounts.attester.key();
+    attestation.player = player;
+    attestation.flags = flags;
+    attestation.issued_at = now;
+    attestation.expires_at = expires_at;
+    attestation.bump = ctx.bumps.compliance_attestation;
+
+    msg!(
+        "Attester {} verified player {} (flags: {:#04b}) until {}",
+        attestation.attester,
+        player,
+        flags,
+        expires_at
+    );
+
+    Ok(())
+}
@@ -1,12 +1,25 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use crate::state::{PokerEscrow, GameStatus};
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{AccessProof, TableAccess, TokenGate, PlayerLimits, ComplianceAttestation, Bankroll, Backing, PokerEscrow, GameStatus, ProgramConfig, PlayerProfile, PlayerRating, SessionToken};
 use crate::errors::PokerError;
+use crate::utils::{verified_collection, transfer_lamports, authorize_player, ed25519_signers};
 
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
+    /// The player, or a session key they authorized. Pays for new accounts.
     #[account(mut)]
-    pub player: Signer<'info>,
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Player joining. Must sign, or have authorized `authority`
+    /// through `session_token`.
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+    
+    /// Session authorizing `authority`, when the player isn't signing
+    #[account(mut)]
+    pub session_token: Option<Account<'info, SessionToken>>,
     
     /// CHECK: Game server account (verified in escrow)
     pub game_server: UncheckedAccount<'info>,
@@ -23,10 +36,105 @@ pub struct JoinGame<'info> {
     )]
     pub poker_escrow: Account<'info, PokerEscrow>,
     
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PlayerProfile::MAX_SIZE,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PlayerRating::MAX_SIZE,
+        seeds = [PlayerRating::SEED_PREFIX, player.key().as_ref()],
+        bump
+    )]
+    pub player_rating: Account<'info, PlayerRating>,
+    
+    /// CHECK: Player's responsible gaming limits. Always passed so they can't
+    /// be skipped; enforced if the player has set any.
+    #[account(
+        mut,
+        seeds = [PlayerLimits::SEED_PREFIX, player.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+    
+    /// Player's bankroll, when buying in from it
+    #[account(mut)]
+    pub bankroll: Option<Account<'info, Bankroll>>,
+    
+    /// Backing funding the buy-in, when backed
+    #[account(mut)]
+    pub backing: Option<Account<'info, Backing>>,
+    
+    /// Player's compliance attestation, for tables that require one
+    pub compliance_attestation: Option<Account<'info, ComplianceAttestation>>,
+    
+    /// Player's token account, for token-gated tables
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metaplex metadata of the gate token's mint, for collection-gated
+    /// tables. Owner, address and layout are checked when parsed.
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, for invite-only tables to read the
+    /// issuer's Ed25519 signature
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<JoinGame>) -> Result<()> {
+/// Where a player's buy-in comes from
+pub enum BuyInSource {
+    Wallet,
+    Bankroll,
+    Backing,
+}
+
+/// Require the player to hold the table's gate token
+fn check_token_gate(
+    token_gate: &TokenGate,
+    player: &Pubkey,
+    token_account: &Option<InterfaceAccount<TokenAccount>>,
+    metadata: &Option<UncheckedAccount>,
+) -> Result<()> {
+    let token_account = token_account.as_ref().ok_or(PokerError::TokenGateNotMet)?;
+    require_keys_eq!(token_account.owner, *player, PokerError::TokenGateNotMet);
+
+    match token_gate {
+        TokenGate::Mint { mint, min_amount } => {
+            require_keys_eq!(token_account.mint, *mint, PokerError::TokenGateNotMet);
+            require!(token_account.amount >= *min_amount, PokerError::TokenGateNotMet);
+        },
+        TokenGate::Collection { collection } => {
+            require!(token_account.amount > 0, PokerError::TokenGateNotMet);
+            let metadata = metadata.as_ref().ok_or(PokerError::InvalidTokenMetadata)?;
+            let token_collection = verified_collection(metadata, &token_account.mint)?;
+            require!(token_collection == Some(*collection), PokerError::TokenGateNotMet);
+        },
+    }
+
+    Ok(())
+}
+
+pub fn handler(
+    ctx: Context<JoinGame>,
+    seat: Option<u8>,
+    access_proof: Option<AccessProof>,
+    source: BuyInSource,
+) -> Result<()> {
     let player_key = ctx.accounts.player.key();
     
     // Extract values we need before borrowing mutably
@@ -41,33 +149,133 @@ pub fn handler(ctx: Context<JoinGame>) -> Result<()> {
     require!(current_status == GameStatus::Pending, PokerError::GameNotPending);
     require!(!is_full, PokerError::GameFull);
     require!(!has_player, PokerError::PlayerAlreadyJoined);
-
-    // Check player has sufficient SOL balance
+    authorize_player(
+        &ctx.accounts.player,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_token.as_mut(),
+        SessionToken::JOIN,
+        buy_in_amount,
+    )?;
+    let invited_by = match (&ctx.accounts.poker_escrow.access, &ctx.accounts.instructions) {
+        (TableAccess::Invite { .. }, Some(instructions)) => ed25519_signers(
+            instructions,
+            &PokerEscrow::invite_message(&ctx.accounts.poker_escrow.key(), &player_key),
+        )?,
+        _ => Vec::new(),
+    };
     require!(
-        ctx.accounts.player.lamports() >= buy_in_amount,
-        PokerError::InsufficientBalance
+        ctx.accounts.poker_escrow.may_join(&player_key, access_proof.as_ref(), &invited_by),
+        PokerError::TableAccessDenied
     );
+    if let Some(token_gate) = &ctx.accounts.poker_escrow.token_gate {
+        check_token_gate(token_gate, &player_key, &ctx.accounts.gate_token_account, &ctx.accounts.gate_metadata)?;
+    }
 
-    // Transfer SOL from player to escrow PDA
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.player.to_account_info(),
-                to: ctx.accounts.poker_escrow.to_account_info(),
-            }
-        ),
-        buy_in_amount,
-    )?;
+    if let Some(attester) = ctx.accounts.poker_escrow.required_attester {
+        let attestation = ctx.accounts.compliance_attestation
+            .as_ref()
+            .ok_or(PokerError::InvalidComplianceAttestation)?;
+        require!(
+            attestation.covers(&attester, &player_key, ctx.accounts.poker_escrow.required_attestation_flags),
+            PokerError::InvalidComplianceAttestation
+        );
+        require!(
+            Clock::get()?.unix_timestamp < attestation.expires_at,
+            PokerError::ComplianceAttestationExpired
+        );
+    }
+
+    // Enforce self-exclusion and buy-in caps
+    let limits_info = ctx.accounts.player_limits.to_account_info();
+    if limits_info.owner == &crate::ID {
+        let mut data = limits_info.try_borrow_mut_data()?;
+        let mut limits = PlayerLimits::try_deserialize(&mut &data[..])?;
+        limits.record_buy_in(buy_in_amount, Clock::get()?.unix_timestamp)?;
+        limits.try_serialize(&mut &mut data[..])?;
+    }
+
+    let payout_to = match source {
+        BuyInSource::Bankroll => {
+            // Debit the bankroll; winnings and refunds are credited back to it
+            let bankroll = ctx.accounts.bankroll.as_ref().ok_or(PokerError::InvalidBankroll)?;
+            require_keys_eq!(bankroll.player, player_key, PokerError::InvalidBankroll);
+            let bankroll_info = bankroll.to_account_info();
+            require!(
+                Bankroll::available(&bankroll_info)? >= buy_in_amount,
+                PokerError::InsufficientBalance
+            );
+            transfer_lamports(&bankroll_info, &ctx.accounts.poker_escrow.to_account_info(), buy_in_amount)?;
+            bankroll.key()
+        },
+        BuyInSource::Backing => {
+            // Winnings and refunds go to the backing, to be split on settlement
+            let escrow_key = ctx.accounts.poker_escrow.key();
+            let backing = ctx.accounts.backing.as_mut().ok_or(PokerError::InvalidBacking)?;
+            require!(
+                backing.player == player_key && backing.poker_escrow == escrow_key && !backing.joined,
+                PokerError::InvalidBacking
+            );
+            require!(backing.is_funded(), PokerError::BackingNotFunded);
+            transfer_lamports(&backing.to_account_info(), &ctx.accounts.poker_escrow.to_account_info(), buy_in_amount)?;
+            backing.joined = true;
+            backing.key()
+        },
+        BuyInSource::Wallet => {
+            // Sessions can only spend from a bankroll or backing
+            require!(ctx.accounts.player.is_signer, PokerError::WalletSignatureRequired);
+
+            // Check player has sufficient SOL balance
+            require!(
+                ctx.accounts.player.lamports() >= buy_in_amount,
+                PokerError::InsufficientBalance
+            );
+
+            // Transfer SOL from player to escrow PDA
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.poker_escrow.to_account_info(),
+                    }
+                ),
+                buy_in_amount,
+            )?;
+            player_key
+        },
+    };
 
     // Add player to game and get current players count
     let poker_escrow = &mut ctx.accounts.poker_escrow;
-    poker_escrow.add_player(player_key, buy_in_amount)?;
+    poker_escrow.add_player(player_key, buy_in_amount, seat, payout_to)?;
     let current_players = poker_escrow.current_players;
 
-    msg!("Player {} joined SOL game {}, players: {}/{}", 
+    // Profiles are created on a player's first join
+    let player_profile = &mut ctx.accounts.player_profile;
+    if player_profile.player == Pubkey::default() {
+        player_profile.player = player_key;
+        player_profile.created_at = Clock::get()?.unix_timestamp;
+        player_profile.bump = ctx.bumps.player_profile;
+    }
+    player_profile.record_join(buy_in_amount)?;
+
+    let player_rating = &mut ctx.accounts.player_rating;
+    if player_rating.player == Pubkey::default() {
+        player_rating.player = player_key;
+        player_rating.rating = PlayerRating::INITIAL_RATING;
+        player_rating.peak_rating = PlayerRating::INITIAL_RATING;
+        player_rating.bump = ctx.bumps.player_rating;
+    }
+
+    let seat = poker_escrow.players
+        .last()
+        .map(|p| p.seat)
+        .ok_or(PokerError::PlayerNotInGame)?;
+
+    msg!("Player {} joined SOL game {} in seat {}, players: {}/{}", 
          player_key,
          game_id,
+         seat,
          current_players,
          max_players);
 
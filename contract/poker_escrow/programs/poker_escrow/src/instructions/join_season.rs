@@ -0,0 +1,54 @@
+// programs/poker_escrow/src/instructions/join_season.rs
+use anchor_lang::prelude::*;
+use crate::state::{Season, SeasonEntry};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct JoinSeason<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Season::SEED_PREFIX, season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+
+    #[account(
+        init,
+        payer = player,
+        space = SeasonEntry::MAX_SIZE,
+        seeds = [SeasonEntry::SEED_PREFIX, season.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<JoinSeason>) -> Result<()> {
+    let clock = Clock::get()?;
+    let season = &mut ctx.accounts.season;
+
+    // Players can sign up ahead of the start, but not after the end
+    require!(
+        !season.closed && clock.unix_timestamp < season.ends_at,
+        PokerError::SeasonNotLive
+    );
+    season.entries = season.entries
+        .checked_add(1)
+        .ok_or(PokerError::MathOverflow)?;
+
+    let entry = &mut ctx.accounts.season_entry;
+    entry.season = season.key();
+    entry.player = ctx.accounts.player.key();
+    entry.points = 0;
+    entry.hands_won = 0;
+    entry.joined_at = clock.unix_timestamp;
+    entry.bump = ctx.bumps.season_entry;
+
+    msg!("Player {} joined season {}", entry.player, season.season_id);
+
+    Ok(())
+}
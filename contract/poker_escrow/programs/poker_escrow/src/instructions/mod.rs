@@ -1,4 +1,109 @@
 // programs/poker_escrow/src/instructions/mod.rs
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod apply_config_change;
+pub use apply_config_change::*;
+
+pub mod cancel_config_change;
+pub use cancel_config_change::*;
+
+pub mod set_guardian;
+pub use set_guardian::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+pub mod transfer_admin;
+pub use transfer_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod register_game_server;
+pub use register_game_server::*;
+
+pub mod top_up_bond;
+pub use top_up_bond::*;
+
+pub mod slash_game_server;
+pub use slash_game_server::*;
+
+pub mod set_operator;
+pub use set_operator::*;
+
+pub mod set_delegate;
+pub use set_delegate::*;
+
+pub mod create_season;
+pub use create_season::*;
+
+pub mod join_season;
+pub use join_season::*;
+
+pub mod close_season;
+pub use close_season::*;
+
+pub mod set_player_limits;
+pub use set_player_limits::*;
+
+pub mod issue_attestation;
+pub use issue_attestation::*;
+
+pub mod revoke_attestation;
+pub use revoke_attestation::*;
+
+pub mod deposit;
+pub use deposit::*;
+
+pub mod request_withdrawal;
+pub use request_withdrawal::*;
+
+pub mod execute_withdrawal;
+pub use execute_withdrawal::*;
+
+pub mod cancel_withdrawal;
+pub use cancel_withdrawal::*;
+
+pub mod initialize_treasury;
+pub use initialize_treasury::*;
+
+pub mod request_treasury_withdrawal;
+pub use request_treasury_withdrawal::*;
+
+pub mod execute_treasury_withdrawal;
+pub use execute_treasury_withdrawal::*;
+
+pub mod cancel_treasury_withdrawal;
+pub use cancel_treasury_withdrawal::*;
+
+pub mod create_backing;
+pub use create_backing::*;
+
+pub mod fund_backing;
+pub use fund_backing::*;
+
+pub mod settle_backing;
+pub use settle_backing::*;
+
+pub mod set_bounty;
+pub use set_bounty::*;
+
+pub mod eliminate_player;
+pub use eliminate_player::*;
+
+pub mod release_bounties;
+pub use release_bounties::*;
+
+pub mod create_session;
+pub use create_session::*;
+
+pub mod revoke_session;
+pub use revoke_session::*;
+
 pub mod create_game;
 pub use create_game::*;
 
@@ -11,8 +116,59 @@ pub use start_game::*;
 pub mod distribute_pot;
 pub use distribute_pot::*;
 
+pub mod set_settlement_window;
+pub use set_settlement_window::*;
+
+pub mod propose_settlement;
+pub use propose_settlement::*;
+
+pub mod dispute_settlement;
+pub use dispute_settlement::*;
+
+pub mod finalize_settlement;
+pub use finalize_settlement::*;
+
+pub mod resolve_dispute;
+pub use resolve_dispute::*;
+
+pub mod expire_settlement;
+pub use expire_settlement::*;
+
+pub mod set_attestation_threshold;
+pub use set_attestation_threshold::*;
+
+pub mod distribute_pot_attested;
+pub use distribute_pot_attested::*;
+
+pub mod settle_channel;
+pub use settle_channel::*;
+
+pub mod challenge_channel;
+pub use challenge_channel::*;
+
+pub mod finalize_channel;
+pub use finalize_channel::*;
+
+pub mod set_payout_signers;
+pub use set_payout_signers::*;
+
 pub mod emergency_refund;
 pub use emergency_refund::*;
 
 pub mod close_game;
-pub use close_game::*;
\ No newline at end of file
+pub use close_game::*;
+
+pub mod set_seat_status;
+pub use set_seat_status::*;
+
+pub mod cancel_game;
+pub use cancel_game::*;
+
+pub mod crank_refund;
+pub use crank_refund::*;
+
+pub mod update_stacks;
+pub use update_stacks::*;
+
+pub mod set_idle_timeout;
+pub use set_idle_timeout::*;
\ No newline at end of file
@@ -0,0 +1,107 @@
+// programs/poker_escrow/src/instructions/propose_settlement.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, Payout, Settlement, SettlementStatus, GameServerProfile, ProgramConfig};
+use crate::errors::PokerError;
+use crate::utils::require_payout_cosigners;
+
+#[derive(Accounts)]
+pub struct ProposeSettlement<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = Settlement::MAX_SIZE,
+        seeds = [
+            Settlement::SEED_PREFIX,
+            poker_escrow.key().as_ref(),
+            poker_escrow.total_hands_played.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: co-signing payout signers, when the payout total
+    // requires them
+}
+
+pub fn handler(
+    ctx: Context<ProposeSettlement>,
+    payouts: Vec<Payout>,
+    hand_rank: u8,
+    hand_description: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    // Validate game state
+    require!(poker_escrow.status == GameStatus::Active, PokerError::GameNotActive);
+    require!(poker_escrow.settlement_window > 0, PokerError::SettlementWindowDisabled);
+    require!(hand_rank <= 9, PokerError::InvalidHandResult); // 0-9 for poker hands
+    require!(!hand_description.is_empty(), PokerError::InvalidHandResult);
+    require!(hand_description.len() <= 32, PokerError::InvalidHandResult);
+    let total = Settlement::validate_payouts(poker_escrow, &payouts)?;
+
+    // Anyone can finalize after the window, so large payouts need their
+    // co-signers now
+    require_payout_cosigners(poker_escrow, &ctx.accounts.operator.key(), ctx.remaining_accounts, total)?;
+
+    // No hands, payouts or idle refunds until the settlement is finalized
+    // or resolved
+    poker_escrow.status = GameStatus::Settling;
+    poker_escrow.touch(clock.unix_timestamp);
+
+    let settlement = &mut ctx.accounts.settlement;
+    settlement.poker_escrow = poker_escrow.key();
+    settlement.hand_index = poker_escrow.total_hands_played;
+    settlement.payouts = payouts;
+    settlement.hand_rank = hand_rank;
+    settlement.hand_description = hand_description;
+    settlement.status = SettlementStatus::Proposed;
+    settlement.proposed_at = clock.unix_timestamp;
+    settlement.challenge_deadline = clock.unix_timestamp
+        .checked_add(poker_escrow.settlement_window)
+        .ok_or(PokerError::MathOverflow)?;
+    settlement.disputed_by = None;
+    settlement.dispute_bond = 0;
+    settlement.bump = ctx.bumps.settlement;
+
+    msg!(
+        "Settlement proposed for hand {} of poker game {}: {} lamports to {} winners, challenge until {}",
+        settlement.hand_index,
+        poker_escrow.game_id,
+        total,
+        settlement.payouts.len(),
+        settlement.challenge_deadline
+    );
+
+    Ok(())
+}
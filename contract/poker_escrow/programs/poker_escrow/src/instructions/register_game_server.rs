@@ -0,0 +1,60 @@
+// programs/poker_escrow/src/instructions/register_game_server.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::{GameServerProfile, ProgramConfig};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct RegisterGameServer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = GameServerProfile::MAX_SIZE,
+        seeds = [GameServerProfile::SEED_PREFIX, authority.key().as_ref()],
+        bump
+    )]
+    pub game_server_profile: Account<'info, GameServerProfile>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterGameServer>, bond: u64) -> Result<()> {
+    require!(bond >= ctx.accounts.config.min_server_bond, PokerError::InsufficientBond);
+
+    // Lock the bond in the profile PDA
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.game_server_profile.to_account_info(),
+            }
+        ),
+        bond,
+    )?;
+
+    let profile = &mut ctx.accounts.game_server_profile;
+    profile.server_id = ctx.accounts.authority.key();
+    profile.authority = ctx.accounts.authority.key();
+    profile.operator = ctx.accounts.authority.key();
+    profile.delegates = Vec::new();
+    profile.bond = bond;
+    profile.games_hosted = 0;
+    profile.disputes_lost = 0;
+    profile.total_slashed = 0;
+    profile.registered_at = Clock::get()?.unix_timestamp;
+    profile.bump = ctx.bumps.game_server_profile;
+
+    msg!("Game server {} registered with {} lamports bond", profile.authority, bond);
+
+    Ok(())
+}
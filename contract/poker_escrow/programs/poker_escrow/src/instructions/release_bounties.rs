@@ -0,0 +1,54 @@
+// programs/poker_escrow/src/instructions/release_bounties.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, GameType, GameServerProfile};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct ReleaseBounties<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+/// Release the bounties still on standing players into the prize pool, so a
+/// knockout tournament that ends with several players left (a chop or a
+/// final table payout) can pay out its whole pot.
+pub fn handler(ctx: Context<ReleaseBounties>) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    require!(poker_escrow.status == GameStatus::Active, PokerError::GameNotActive);
+    require!(
+        poker_escrow.game_type == GameType::KnockoutTournament,
+        PokerError::InvalidGameType
+    );
+
+    let released = poker_escrow.release_bounties();
+    poker_escrow.touch(Clock::get()?.unix_timestamp);
+
+    msg!(
+        "Released {} lamports of bounties in poker game {}, prize pool: {} lamports",
+        released,
+        poker_escrow.game_id,
+        poker_escrow.prize_pool()
+    );
+
+    Ok(())
+}
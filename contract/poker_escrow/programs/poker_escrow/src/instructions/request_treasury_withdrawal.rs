@@ -0,0 +1,65 @@
+// programs/poker_escrow/src/instructions/request_treasury_withdrawal.rs
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Treasury, Bankroll, WithdrawalRequest};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct RequestTreasuryWithdrawal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PokerError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = WithdrawalRequest::MAX_SIZE,
+        seeds = [WithdrawalRequest::SEED_PREFIX, treasury.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RequestTreasuryWithdrawal>,
+    destination: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, PokerError::InvalidAmount);
+    require!(
+        Bankroll::available(&ctx.accounts.treasury.to_account_info())? >= amount,
+        PokerError::InsufficientBalance
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let request = &mut ctx.accounts.withdrawal_request;
+    request.source = ctx.accounts.treasury.key();
+    request.destination = destination;
+    request.amount = amount;
+    request.requested_by = ctx.accounts.admin.key();
+    request.requested_at = now;
+    request.unlocks_at = now + Treasury::WITHDRAWAL_DELAY;
+    request.bump = ctx.bumps.withdrawal_request;
+
+    msg!(
+        "Treasury withdrawal of {} lamports to {} requested, unlocks at {}",
+        amount,
+        destination,
+        request.unlocks_at
+    );
+
+    Ok(())
+}
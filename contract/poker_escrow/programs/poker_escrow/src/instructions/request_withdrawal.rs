@@ -0,0 +1,72 @@
+// programs/poker_escrow/src/instructions/request_withdrawal.rs
+use anchor_lang::prelude::*;
+use crate::state::{Bankroll, SessionToken, WithdrawalRequest};
+use crate::errors::PokerError;
+use crate::utils::authorize_player;
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    /// The player, or a session key they authorized. Pays for the request.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Player cashing out, always the destination. Must sign, or have
+    /// authorized `authority` through `session_token`.
+    pub player: UncheckedAccount<'info>,
+
+    /// Session authorizing `authority`, when the player isn't signing
+    #[account(mut)]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(
+        seeds = [Bankroll::SEED_PREFIX, player.key().as_ref()],
+        bump = bankroll.bump,
+        has_one = player,
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = WithdrawalRequest::MAX_SIZE,
+        seeds = [WithdrawalRequest::SEED_PREFIX, bankroll.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+    require!(amount > 0, PokerError::InvalidAmount);
+    authorize_player(
+        &ctx.accounts.player,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_token.as_mut(),
+        SessionToken::CASH_OUT,
+        0,
+    )?;
+    require!(
+        Bankroll::available(&ctx.accounts.bankroll.to_account_info())? >= amount,
+        PokerError::InsufficientBalance
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let request = &mut ctx.accounts.withdrawal_request;
+    request.source = ctx.accounts.bankroll.key();
+    request.destination = ctx.accounts.player.key();
+    request.amount = amount;
+    request.requested_by = ctx.accounts.authority.key();
+    request.requested_at = now;
+    request.unlocks_at = now + Bankroll::WITHDRAWAL_DELAY;
+    request.bump = ctx.bumps.withdrawal_request;
+
+    msg!(
+        "Player {} requested a withdrawal of {} lamports, unlocks at {}",
+        request.destination,
+        amount,
+        request.unlocks_at
+    );
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+// programs/poker_escrow/src/instructions/resolve_dispute.rs
+use anchor_lang::prelude::*;
+use crate::state::{
+    PokerEscrow, GameStatus, GameServerProfile, Payout, ProgramConfig, Settlement, SettlementStatus, Season,
+};
+use crate::errors::PokerError;
+use crate::utils::{WINNER_ACCOUNTS, live_season, pay_winners, finish_hand, transfer_lamports};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    /// Admin or arbiter ruling on the dispute
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.is_arbiter(&arbiter.key()) @ PokerError::UnauthorizedArbiter,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    /// CHECK: Game server owner, receives rent on close
+    #[account(mut, address = game_server.authority @ PokerError::UnauthorizedGameServer)]
+    pub server_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        mut,
+        close = server_authority,
+        seeds = [
+            Settlement::SEED_PREFIX,
+            poker_escrow.key().as_ref(),
+            settlement.hand_index.to_le_bytes().as_ref()
+        ],
+        bump = settlement.bump,
+        has_one = poker_escrow,
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    /// CHECK: Player who disputed, verified against the settlement
+    #[account(
+        mut,
+        constraint = settlement.disputed_by == Some(disputer.key()) @ PokerError::SettlementNotDisputed,
+    )]
+    pub disputer: UncheckedAccount<'info>,
+
+    /// CHECK: Protocol treasury, verified against the program config
+    #[account(mut, address = config.treasury @ PokerError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Season the game scores for, required when the game has one
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (winner payout account, player profile, season entry or
+    // program id) triples, writable, in final payout order,
+    // then every player's rating account, in the order they joined, when
+    // this completes a rated game
+}
+
+/// Resolve a disputed settlement. `corrected_payouts` upholds the dispute and
+/// replaces the server's payouts; `None` rejects it and pays as proposed.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+    corrected_payouts: Option<Vec<Payout>>,
+) -> Result<()> {
+    require!(ctx.accounts.poker_escrow.status == GameStatus::Settling, PokerError::GameNotActive);
+    require!(
+        ctx.accounts.settlement.status == SettlementStatus::Disputed,
+        PokerError::SettlementNotDisputed
+    );
+
+    let game_id = ctx.accounts.poker_escrow.game_id;
+    let hand_index = ctx.accounts.settlement.hand_index;
+    let dispute_bond = ctx.accounts.settlement.dispute_bond;
+    let settlement_info = ctx.accounts.settlement.to_account_info();
+
+    let payouts = match corrected_payouts {
+        Some(payouts) => {
+            // Dispute upheld: bond back to the player, strike against the server
+            Settlement::validate_payouts(&ctx.accounts.poker_escrow, &payouts)?;
+            transfer_lamports(&settlement_info, &ctx.accounts.disputer.to_account_info(), dispute_bond)?;
+
+            let profile = &mut ctx.accounts.game_server;
+            profile.disputes_lost = profile.disputes_lost
+                .checked_add(1)
+                .ok_or(PokerError::MathOverflow)?;
+
+            msg!("Dispute upheld for hand {} in poker game {}", hand_index, game_id);
+            payouts
+        }
+        None => {
+            // Dispute rejected: bond forfeited to the treasury
+            transfer_lamports(&settlement_info, &ctx.accounts.treasury.to_account_info(), dispute_bond)?;
+
+            msg!("Dispute rejected for hand {} in poker game {}", hand_index, game_id);
+            ctx.accounts.settlement.payouts.clone()
+        }
+    };
+
+    // Play resumes once the hand is paid
+    ctx.accounts.poker_escrow.status = GameStatus::Active;
+
+    let now = Clock::get()?.unix_timestamp;
+    let season = live_season(&ctx.accounts.poker_escrow, ctx.accounts.season.as_mut(), now)?;
+    let accounts = ctx.remaining_accounts;
+    let (winners, ratings) = accounts.split_at((payouts.len() * WINNER_ACCOUNTS).min(accounts.len()));
+
+    pay_winners(
+        &mut ctx.accounts.poker_escrow,
+        &payouts,
+        winners,
+        &ctx.accounts.treasury.to_account_info(),
+        season,
+        ctx.accounts.settlement.hand_rank,
+        &ctx.accounts.settlement.hand_description,
+    )?;
+
+    finish_hand(&mut ctx.accounts.poker_escrow, &ctx.accounts.server_authority.to_account_info(), ratings)?;
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+// programs/poker_escrow/src/instructions/revoke_attestation.rs
+use anchor_lang::prelude::*;
+use crate::state::ComplianceAttestation;
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(mut)]
+    pub attester: Signer<'info>,
+
+    #[account(
+        mut,
+        close = attester,
+        seeds = [
+            ComplianceAttestation::SEED_PREFIX,
+            attester.key().as_ref(),
+            compliance_attestation.player.as_ref()
+        ],
+        bump = compliance_attestation.bump,
+        has_one = attester,
+    )]
+    pub compliance_attestation: Account<'info, ComplianceAttestation>,
+}
+
+pub fn handler(ctx: Context<RevokeAttestation>) -> Result<()> {
+    msg!(
+        "Attester {} revoked attestation for player {}",
+        ctx.accounts.attester.key(),
+        ctx.accounts.compliance_attestation.player
+    );
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+// programs/poker_escrow/src/instructions/revoke_session.rs
+use anchor_lang::prelude::*;
+use crate::state::SessionToken;
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [
+            SessionToken::SEED_PREFIX,
+            player.key().as_ref(),
+            session_token.session_key.as_ref()
+        ],
+        bump = session_token.bump,
+        has_one = player,
+    )]
+    pub session_token: Account<'info, SessionToken>,
+}
+
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    msg!(
+        "Player {} revoked session key {}",
+        ctx.accounts.player.key(),
+        ctx.accounts.session_token.session_key
+    );
+
+    Ok(())
+}
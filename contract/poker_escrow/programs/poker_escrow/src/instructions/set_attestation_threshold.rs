@@ -0,0 +1,52 @@
+// programs/poker_escrow/src/instructions/set_attestation_threshold.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameServerProfile};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetAttestationThreshold<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+pub fn handler(ctx: Context<SetAttestationThreshold>, attestation_threshold: u8) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    require!(poker_escrow.can_configure(), PokerError::GameSettingsLocked);
+
+    // Never satisfiable by a single party, never more than players plus server
+    require!(
+        attestation_threshold == 0 ||
+        (2..=poker_escrow.max_players + 1).contains(&attestation_threshold),
+        PokerError::InvalidAttestationThreshold
+    );
+
+    poker_escrow.attestation_threshold = attestation_threshold;
+
+    msg!(
+        "Poker game {} attestation threshold set to {}",
+        poker_escrow.game_id,
+        attestation_threshold
+    );
+
+    Ok(())
+}
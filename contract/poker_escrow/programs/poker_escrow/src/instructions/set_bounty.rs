@@ -0,0 +1,56 @@
+// programs/poker_escrow/src/instructions/set_bounty.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameType, GameServerProfile, ProgramConfig};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetBounty<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+pub fn handler(ctx: Context<SetBounty>, bounty_bps: u16, progressive: bool) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    // Bounties are taken from buy-ins as players join
+    require!(poker_escrow.can_configure(), PokerError::GameSettingsLocked);
+    require!(
+        poker_escrow.game_type == GameType::KnockoutTournament,
+        PokerError::InvalidGameType
+    );
+    require!(
+        bounty_bps > 0 && bounty_bps as u64 <= ProgramConfig::BPS_DENOMINATOR,
+        PokerError::InvalidBounty
+    );
+
+    poker_escrow.bounty_bps = bounty_bps;
+    poker_escrow.progressive_bounty = progressive;
+
+    msg!(
+        "Poker game {} holds {} bps of each buy-in as a {}bounty",
+        poker_escrow.game_id,
+        bounty_bps,
+        if progressive { "progressive " } else { "" }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// programs/poker_escrow/src/instructions/set_delegate.rs
+use anchor_lang::prelude::*;
+use crate::state::{GameServerProfile, Delegate};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        has_one = authority @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+}
+
+/// Grant a hot key delegate permissions, or revoke it with `permissions == 0`
+pub fn handler(ctx: Context<SetDelegate>, delegate: Pubkey, permissions: u8) -> Result<()> {
+    require!(
+        permissions & !GameServerProfile::DELEGATE_ALL == 0,
+        PokerError::InvalidDelegatePermissions
+    );
+
+    let game_server = &mut ctx.accounts.game_server;
+    game_server.delegates.retain(|d| d.key != delegate);
+
+    if permissions != 0 {
+        require!(
+            game_server.delegates.len() < GameServerProfile::MAX_DELEGATES,
+            PokerError::TooManyDelegates
+        );
+        game_server.delegates.push(Delegate { key: delegate, permissions });
+        msg!("Game server {} delegate {} permissions: {}", game_server.key(), delegate, permissions);
+    } else {
+        msg!("Game server {} delegate {} revoked", game_server.key(), delegate);
+    }
+
+    Ok(())
+}
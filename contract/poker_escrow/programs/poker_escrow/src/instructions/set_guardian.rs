@@ -0,0 +1,29 @@
+// programs/poker_escrow/src/instructions/set_guardian.rs
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    /// Current guardian only, so a compromised admin can't replace the key
+    /// meant to overrule it
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = guardian @ PokerError::UnauthorizedGuardian,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.guardian = guardian;
+
+    msg!("Program guardian set to {}", guardian);
+
+    Ok(())
+}
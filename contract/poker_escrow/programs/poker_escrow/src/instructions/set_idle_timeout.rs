@@ -0,0 +1,50 @@
+// programs/poker_escrow/src/instructions/set_idle_timeout.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameServerProfile};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetIdleTimeout<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+pub fn handler(ctx: Context<SetIdleTimeout>, idle_timeout: i64) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    require!(poker_escrow.can_configure(), PokerError::GameSettingsLocked);
+    // Long enough to ride out a server restart, short enough not to strand deposits
+    require!(
+        (PokerEscrow::MIN_IDLE_TIMEOUT..=PokerEscrow::MAX_IDLE_TIMEOUT).contains(&idle_timeout),
+        PokerError::InvalidIdleTimeout
+    );
+
+    poker_escrow.idle_timeout = idle_timeout;
+
+    msg!(
+        "Poker game {} idle refund timeout set to {} seconds",
+        poker_escrow.game_id,
+        idle_timeout
+    );
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// programs/poker_escrow/src/instructions/set_operator.rs
+use anchor_lang::prelude::*;
+use crate::state::GameServerProfile;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        has_one = authority @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+}
+
+/// Rotate the operational signer. Live escrows are seeded by the profile,
+/// so they remain operable with the new key.
+pub fn handler(ctx: Context<SetOperator>, new_operator: Pubkey) -> Result<()> {
+    let game_server = &mut ctx.accounts.game_server;
+    let previous_operator = game_server.operator;
+    game_server.operator = new_operator;
+
+    msg!(
+        "Game server {} operator rotated: {} -> {}",
+        game_server.key(),
+        previous_operator,
+        new_operator
+    );
+
+    Ok(())
+}
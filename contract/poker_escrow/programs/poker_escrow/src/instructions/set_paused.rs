@@ -0,0 +1,32 @@
+// programs/poker_escrow/src/instructions/set_paused.rs
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(constraint = config.is_guardian(&authority.key()) @ PokerError::UnauthorizedGuardian)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Refund paths never read this flag, so players can always exit
+    config.paused = paused;
+
+    msg!(
+        "Program {} by {}",
+        if paused { "paused" } else { "unpaused" },
+        ctx.accounts.authority.key()
+    );
+
+    Ok(())
+}
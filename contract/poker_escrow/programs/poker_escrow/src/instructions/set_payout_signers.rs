@@ -0,0 +1,70 @@
+// programs/poker_escrow/src/instructions/set_payout_signers.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameServerProfile};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetPayoutSigners<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+pub fn handler(
+    ctx: Context<SetPayoutSigners>,
+    payout_signers: Vec<Pubkey>,
+    payout_threshold: u8,
+    large_payout_amount: u64,
+) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    require!(poker_escrow.can_configure(), PokerError::GameSettingsLocked);
+    require!(
+        payout_signers.len() <= PokerEscrow::MAX_PAYOUT_SIGNERS,
+        PokerError::InvalidPayoutSigners
+    );
+    // A key listed twice would count twice toward the threshold
+    require!(
+        payout_signers
+            .iter()
+            .enumerate()
+            .all(|(i, key)| !payout_signers[..i].contains(key)),
+        PokerError::InvalidPayoutSigners
+    );
+    require!(
+        payout_threshold as usize <= payout_signers.len(),
+        PokerError::InvalidPayoutSigners
+    );
+
+    poker_escrow.payout_signers = payout_signers;
+    poker_escrow.payout_threshold = payout_threshold;
+    poker_escrow.large_payout_amount = large_payout_amount;
+
+    msg!(
+        "Poker game {} payouts of {}+ lamports need {} of {} operator signatures",
+        poker_escrow.game_id,
+        large_payout_amount,
+        payout_threshold,
+        poker_escrow.payout_signers.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+// programs/poker_escrow/src/instructions/set_player_limits.rs
+use anchor_lang::prelude::*;
+use crate::state::{PlayerLimits, LimitSettings};
+
+#[derive(Accounts)]
+pub struct SetPlayerLimits<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerLimits::MAX_SIZE,
+        seeds = [PlayerLimits::SEED_PREFIX, player.key().as_ref()],
+        bump
+    )]
+    pub player_limits: Account<'info, PlayerLimits>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetPlayerLimits>, settings: LimitSettings) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let player_limits = &mut ctx.accounts.player_limits;
+
+    if player_limits.player == Pubkey::default() {
+        // First limits apply as-is, there is nothing to loosen yet
+        player_limits.player = ctx.accounts.player.key();
+        player_limits.settings = settings;
+        player_limits.bump = ctx.bumps.player_limits;
+        msg!("Limits set for player {}: {:?}", player_limits.player, settings);
+        return Ok(());
+    }
+
+    if player_limits.request(settings, now) {
+        msg!(
+            "Limits for player {} loosen to {:?} at {}, now {:?}",
+            player_limits.player,
+            settings,
+            player_limits.pending_effective_at,
+            player_limits.settings
+        );
+    } else {
+        msg!("Limits set for player {}: {:?}", player_limits.player, settings);
+    }
+
+    Ok(())
+}
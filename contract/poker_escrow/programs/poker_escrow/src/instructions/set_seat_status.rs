@@ -0,0 +1,59 @@
+// programs/poker_escrow/src/instructions/set_seat_status.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, GameType, SeatStatus, GameServerProfile};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetSeatStatus<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+pub fn handler(
+    ctx: Context<SetSeatStatus>,
+    player: Pubkey,
+    status: SeatStatus,
+) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    // Seat status only matters for multi-hand games in progress
+    require!(poker_escrow.status == GameStatus::Active, PokerError::GameNotActive);
+    require!(poker_escrow.game_type != GameType::SingleHand, PokerError::InvalidGameType);
+
+    // Only tournaments eliminate players, cash game players simply sit out.
+    // Knockout eliminations go through `eliminate_player` to pay the bounty.
+    if status == SeatStatus::Eliminated {
+        require!(poker_escrow.game_type == GameType::Tournament, PokerError::InvalidGameType);
+    }
+
+    poker_escrow.set_seat_status(&player, status)?;
+    poker_escrow.touch(Clock::get()?.unix_timestamp);
+
+    msg!(
+        "Player {} in poker game {} is now {:?}",
+        player,
+        poker_escrow.game_id,
+        status
+    );
+
+    Ok(())
+}
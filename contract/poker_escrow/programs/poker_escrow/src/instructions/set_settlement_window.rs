@@ -0,0 +1,58 @@
+// programs/poker_escrow/src/instructions/set_settlement_window.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameServerProfile};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct SetSettlementWindow<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+pub fn handler(
+    ctx: Context<SetSettlementWindow>,
+    settlement_window: i64,
+    dispute_bond: u64,
+) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    require!(poker_escrow.can_configure(), PokerError::GameSettingsLocked);
+    // Zero pays out immediately; otherwise players get a real chance to dispute
+    require!(
+        settlement_window == 0 ||
+        (PokerEscrow::MIN_SETTLEMENT_WINDOW..=PokerEscrow::MAX_SETTLEMENT_WINDOW)
+            .contains(&settlement_window),
+        PokerError::InvalidSettlementWindow
+    );
+
+    poker_escrow.settlement_window = settlement_window;
+    poker_escrow.dispute_bond = dispute_bond;
+
+    msg!(
+        "Poker game {} settlement window set to {} seconds, dispute bond {} lamports",
+        poker_escrow.game_id,
+        settlement_window,
+        dispute_bond
+    );
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+// programs/poker_escrow/src/instructions/settle_backing.rs
+use anchor_lang::prelude::*;
+use crate::state::{Backing, Bankroll, GameStatus, PokerEscrow, ProgramConfig};
+use crate::errors::PokerError;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct SettleBacking<'info> {
+    /// Anyone once the player's game is over; before the player joins only
+    /// the player, to call the backing off
+    pub authority: Signer<'info>,
+
+    /// CHECK: Backed player, receives what the backers don't plus the rent
+    #[account(mut, address = backing.player @ PokerError::InvalidBacking)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [Backing::SEED_PREFIX, backing.poker_escrow.as_ref(), backing.player.as_ref()],
+        bump = backing.bump,
+    )]
+    pub backing: Account<'info, Backing>,
+
+    /// CHECK: The backed game, possibly already closed. Read by hand.
+    #[account(address = backing.poker_escrow @ PokerError::InvalidBacking)]
+    pub poker_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Whether the backed game can no longer pay `player`
+fn game_over_for(escrow_info: &AccountInfo, player: &Pubkey) -> Result<bool> {
+    if escrow_info.owner != &crate::ID || escrow_info.data_is_empty() {
+        return Ok(true);
+    }
+    let escrow = PokerEscrow::try_deserialize(&mut &escrow_info.try_borrow_data()?[..])?;
+    Ok(escrow.status == GameStatus::Completed || !escrow.has_player(player))
+}
+
+/// Pay each backer, whose wallets are passed in `remaining_accounts` in the
+/// order they were listed, their stake and profit share. The player gets the
+/// rest when the backing closes.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleBacking<'info>>) -> Result<()> {
+    let backing = &ctx.accounts.backing;
+
+    // Calling off a backing before the join only returns stakes, like a refund
+    require!(!backing.joined || !ctx.accounts.config.paused, PokerError::ProgramPaused);
+
+    let game_over = game_over_for(&ctx.accounts.poker_escrow, &backing.player)?;
+    let settleable = if backing.joined {
+        game_over
+    } else {
+        ctx.accounts.authority.key() == backing.player || game_over
+    };
+    require!(settleable, PokerError::BackingNotSettleable);
+
+    let backers = ctx.remaining_accounts;
+    require!(backers.len() == backing.backers.len(), PokerError::InvalidBacking);
+
+    let backing_info = backing.to_account_info();
+    let total = Bankroll::available(&backing_info)?;
+    let payouts = backing.backer_payouts(total);
+
+    for ((backer, wallet), amount) in backing.backers.iter().zip(backers).zip(payouts) {
+        require_keys_eq!(*wallet.key, backer.terms.backer, PokerError::InvalidBacking);
+        if amount == 0 {
+            continue;
+        }
+        require!(wallet.is_writable, PokerError::InvalidBacking);
+        transfer_lamports(&backing_info, wallet, amount)?;
+
+        msg!("Paid backer {} {} lamports", backer.terms.backer, amount);
+    }
+
+    msg!(
+        "Settled backing of player {} ({} lamports received)",
+        backing.player,
+        total
+    );
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+// programs/poker_escrow/src/instructions/settle_channel.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{PokerEscrow, GameStatus, GameType, Channel, StackUpdate, GameServerProfile, ProgramConfig};
+use crate::errors::PokerError;
+use crate::utils::require_fully_signed;
+
+#[derive(Accounts)]
+pub struct SettleChannel<'info> {
+    /// Any player or the server can start settling the channel
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        init,
+        payer = submitter,
+        space = Channel::MAX_SIZE,
+        seeds = [Channel::SEED_PREFIX, poker_escrow.key().as_ref()],
+        bump
+    )]
+    pub channel: Account<'info, Channel>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Instructions sysvar, used to read the Ed25519 precompile instructions
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SettleChannel>, nonce: u64, stacks: Vec<StackUpdate>) -> Result<()> {
+    let clock = Clock::get()?;
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    // Channels replace per-hand payouts in cash games, and need a challenge window
+    require!(poker_escrow.status == GameStatus::Active, PokerError::GameNotActive);
+    require!(poker_escrow.game_type == GameType::CashGame, PokerError::InvalidGameType);
+    require!(poker_escrow.settlement_window > 0, PokerError::SettlementWindowDisabled);
+
+    Channel::validate_stacks(poker_escrow, &stacks)?;
+    require_fully_signed(
+        poker_escrow,
+        &ctx.accounts.instructions.to_account_info(),
+        &ctx.accounts.game_server,
+        &ctx.accounts.submitter.key(),
+        &Channel::state_message(&poker_escrow.key(), nonce, &stacks),
+    )?;
+
+    // No more hands or payouts while the channel settles
+    poker_escrow.status = GameStatus::Settling;
+
+    let channel = &mut ctx.accounts.channel;
+    channel.poker_escrow = poker_escrow.key();
+    channel.nonce = nonce;
+    channel.stacks = stacks;
+    channel.submitted_at = clock.unix_timestamp;
+    channel.challenge_deadline = clock.unix_timestamp
+        .checked_add(poker_escrow.settlement_window)
+        .ok_or(PokerError::MathOverflow)?;
+    channel.rent_payer = ctx.accounts.submitter.key();
+    channel.bump = ctx.bumps.channel;
+
+    msg!(
+        "Channel settlement started for poker game {} at nonce {}, challenge until {}",
+        poker_escrow.game_id,
+        nonce,
+        channel.challenge_deadline
+    );
+
+    Ok(())
+}
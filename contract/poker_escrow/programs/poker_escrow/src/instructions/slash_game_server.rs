@@ -0,0 +1,89 @@
+// programs/poker_escrow/src/instructions/slash_game_server.rs
+use anchor_lang::prelude::*;
+use crate::state::{GameServerProfile, PokerEscrow, ProgramConfig};
+use crate::errors::PokerError;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct SlashGameServer<'info> {
+    /// Admin or arbiter ruling on the dispute
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.is_arbiter(&arbiter.key()) @ PokerError::UnauthorizedArbiter,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [GameServerProfile::SEED_PREFIX, game_server_profile.server_id.as_ref()],
+        bump = game_server_profile.bump,
+    )]
+    pub game_server_profile: Account<'info, GameServerProfile>,
+
+    /// Game the server is slashed over, whose players are compensated
+    #[account(
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server_profile.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        constraint = poker_escrow.game_server == game_server_profile.key() @ PokerError::UnauthorizedGameServer,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+    // remaining_accounts: payout accounts of the wronged players in
+    // `poker_escrow` (writable), one per entry in `amounts`
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SlashGameServer<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    require!(
+        !amounts.is_empty() && amounts.len() == ctx.remaining_accounts.len(),
+        PokerError::InvalidCompensationList
+    );
+
+    let total: u64 = amounts
+        .iter()
+        .try_fold(0u64, |acc, amount| acc.checked_add(*amount))
+        .ok_or(PokerError::MathOverflow)?;
+    require!(total <= ctx.accounts.game_server_profile.bond, PokerError::SlashExceedsBond);
+
+    // Pay each wronged player of the game out of the bond, once
+    let poker_escrow = &ctx.accounts.poker_escrow;
+    let profile_info = ctx.accounts.game_server_profile.to_account_info();
+    for (i, (player, amount)) in ctx.remaining_accounts.iter().zip(amounts.iter()).enumerate() {
+        require!(player.is_writable, PokerError::InvalidRefundAccount);
+        require!(
+            poker_escrow.players.iter().any(|p| p.payout_to == *player.key) &&
+            !ctx.remaining_accounts[..i].iter().any(|other| other.key == player.key),
+            PokerError::InvalidCompensationRecipient
+        );
+        transfer_lamports(&profile_info, player, *amount)?;
+        msg!("Compensated player {} with {} lamports", player.key, amount);
+    }
+
+    let profile = &mut ctx.accounts.game_server_profile;
+    profile.bond -= total;
+    profile.total_slashed = profile.total_slashed
+        .checked_add(total)
+        .ok_or(PokerError::MathOverflow)?;
+    profile.disputes_lost = profile.disputes_lost
+        .checked_add(1)
+        .ok_or(PokerError::MathOverflow)?;
+
+    msg!(
+        "Game server {} slashed {} lamports, bond left: {}, disputes lost: {}",
+        profile.authority,
+        total,
+        profile.bond,
+        profile.disputes_lost
+    );
+
+    Ok(())
+}
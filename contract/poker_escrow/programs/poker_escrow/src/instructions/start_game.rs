@@ -1,12 +1,20 @@
 // programs/poker_escrow/src/instructions/start_game.rs
 use anchor_lang::prelude::*;
-use crate::state::{PokerEscrow, GameStatus, GameType};
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use crate::state::{PokerEscrow, GameStatus, GameType, GameServerProfile, ProgramConfig};
 use crate::errors::PokerError;
 
 #[derive(Accounts)]
 pub struct StartGame<'info> {
     #[account(mut)]
-    pub game_server: Signer<'info>,
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_perform(&operator.key(), GameServerProfile::DELEGATE_START_GAME) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
     
     #[account(
         mut,
@@ -19,12 +27,42 @@ pub struct StartGame<'info> {
         has_one = game_server,
     )]
     pub poker_escrow: Account<'info, PokerEscrow>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.paused @ PokerError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: SlotHashes sysvar, read manually since it is too large to deserialize
+    #[account(address = slot_hashes::ID)]
+    pub recent_slot_hashes: UncheckedAccount<'info>,
+}
+
+/// Read the most recent (slot, hash) entry from the SlotHashes sysvar
+fn most_recent_slot_hash(slot_hashes: &AccountInfo) -> Result<(u64, [u8; 32])> {
+    let data = slot_hashes.try_borrow_data()?;
+    // Layout: u64 entry count, then (u64 slot, [u8; 32] hash) entries, newest first
+    require!(data.len() >= 48, PokerError::InvalidSlotHashes);
+
+    let mut count = [0u8; 8];
+    count.copy_from_slice(&data[0..8]);
+    require!(u64::from_le_bytes(count) > 0, PokerError::InvalidSlotHashes);
+
+    let mut slot = [0u8; 8];
+    slot.copy_from_slice(&data[8..16]);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&data[16..48]);
+
+    Ok((u64::from_le_bytes(slot), hash))
 }
 
 pub fn handler(
     ctx: Context<StartGame>,
     hand_identifier: Option<String>,
 ) -> Result<()> {
+    let (seed_slot, slot_hash) = most_recent_slot_hash(&ctx.accounts.recent_slot_hashes)?;
     let poker_escrow = &mut ctx.accounts.poker_escrow;
     
     // Validate game can start
@@ -46,8 +84,17 @@ pub fn handler(
     let hand_identifier_clone = poker_escrow.hand_identifier.clone();
 
     // Update game status and timing
+    let now = Clock::get()?.unix_timestamp;
     poker_escrow.status = GameStatus::Active;
-    poker_escrow.started_at = Some(Clock::get()?.unix_timestamp);
+    poker_escrow.started_at = Some(now);
+    poker_escrow.touch(now);
+
+    // Pick the initial dealer button from the slot hash and seated players
+    let dealer_seed = poker_escrow.compute_dealer_seed(&slot_hash);
+    poker_escrow.dealer_seed = dealer_seed;
+    poker_escrow.dealer_seed_slot = seed_slot;
+    poker_escrow.dealer_position = poker_escrow.dealer_from_seed(&dealer_seed);
+    let dealer_position = poker_escrow.dealer_position;
     
     // Start new hand (increments hand number) 
     poker_escrow.start_new_hand(hand_identifier_clone.clone())?;
@@ -56,15 +103,18 @@ pub fn handler(
         GameType::SingleHand => "Single Hand",
         GameType::Tournament => "Tournament",
         GameType::CashGame => "Cash Game",
+        GameType::KnockoutTournament => "Knockout Tournament",
     };
 
     msg!(
-        "{} poker game {} started with {} players, pot: {} lamports, hand: {}", 
+        "{} poker game {} started with {} players, pot: {} lamports, hand: {}, dealer: {} (seed slot {})", 
         game_type_str,
         game_id,
         current_players,
         total_pot,
-        hand_identifier_clone
+        hand_identifier_clone,
+        dealer_position,
+        seed_slot
     );
 
     Ok(())
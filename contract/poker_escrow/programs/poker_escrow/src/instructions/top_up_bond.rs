@@ -0,0 +1,43 @@
+// programs/poker_escrow/src/instructions/top_up_bond.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::GameServerProfile;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct TopUpBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GameServerProfile::SEED_PREFIX, game_server_profile.server_id.as_ref()],
+        bump = game_server_profile.bump,
+        has_one = authority @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server_profile: Account<'info, GameServerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<TopUpBond>, amount: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.game_server_profile.to_account_info(),
+            }
+        ),
+        amount,
+    )?;
+
+    let profile = &mut ctx.accounts.game_server_profile;
+    profile.bond = profile.bond
+        .checked_add(amount)
+        .ok_or(PokerError::MathOverflow)?;
+
+    msg!("Game server {} bond topped up to {} lamports", profile.authority, profile.bond);
+
+    Ok(())
+}
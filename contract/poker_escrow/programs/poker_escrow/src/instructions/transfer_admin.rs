@@ -0,0 +1,30 @@
+// programs/poker_escrow/src/instructions/transfer_admin.rs
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PokerError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Step one: nominate a new admin (None cancels a pending transfer)
+pub fn handler(ctx: Context<TransferAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = new_admin;
+
+    match new_admin {
+        Some(new_admin) => msg!("Admin transfer proposed: {} -> {}", config.admin, new_admin),
+        None => msg!("Admin transfer cancelled"),
+    }
+
+    Ok(())
+}
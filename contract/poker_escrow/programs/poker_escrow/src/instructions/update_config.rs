@@ -0,0 +1,77 @@
+// programs/poker_escrow/src/instructions/update_config.rs
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, ConfigChange, GameType, Treasury};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PokerError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    treasury: Option<Pubkey>,
+    fee_bps: Option<u16>,
+    allowed_game_types: Option<Vec<GameType>>,
+    max_buy_in: Option<u64>,
+    min_server_bond: Option<u64>,
+    arbiter: Option<Pubkey>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let now = Clock::get()?.unix_timestamp;
+
+    if let Some(treasury) = treasury {
+        // Once fees go to the vault they can only leave it through the
+        // time-locked withdrawal queue
+        let (vault, _) = Pubkey::find_program_address(&[Treasury::SEED_PREFIX], &crate::ID);
+        require!(config.treasury != vault, PokerError::InvalidTreasury);
+        config.treasury = treasury;
+    }
+    if let Some(allowed_game_types) = allowed_game_types {
+        config.allowed_game_types = allowed_game_types;
+    }
+    if let Some(max_buy_in) = max_buy_in {
+        config.max_buy_in = max_buy_in;
+    }
+    if let Some(min_server_bond) = min_server_bond {
+        config.min_server_bond = min_server_bond;
+    }
+    config.validate()?;
+
+    // Fee and arbiter wait out the delay in `apply_config_change`, replacing
+    // any change already queued
+    if fee_bps.is_some() || arbiter.is_some() {
+        require!(
+            fee_bps.is_none_or(|fee| fee <= ProgramConfig::MAX_FEE_BPS),
+            PokerError::InvalidFee
+        );
+        config.pending_change = Some(ConfigChange {
+            fee_bps,
+            arbiter,
+            applies_at: now + ProgramConfig::CHANGE_DELAY,
+        });
+        msg!(
+            "Config change queued: fee={:?} bps, arbiter={:?}, applies at {}",
+            fee_bps,
+            arbiter,
+            now + ProgramConfig::CHANGE_DELAY
+        );
+    }
+
+    msg!(
+        "Program config updated: treasury={}, fee={} bps, max_buy_in={} lamports",
+        config.treasury,
+        config.fee_bps,
+        config.max_buy_in
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+// programs/poker_escrow/src/instructions/update_stacks.rs
+use anchor_lang::prelude::*;
+use crate::state::{PokerEscrow, GameStatus, GameType, StackUpdate, GameServerProfile};
+use crate::errors::PokerError;
+
+#[derive(Accounts)]
+pub struct UpdateStacks<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GameServerProfile::SEED_PREFIX, game_server.server_id.as_ref()],
+        bump = game_server.bump,
+        constraint = game_server.can_operate(&operator.key()) @ PokerError::UnauthorizedGameServer,
+    )]
+    pub game_server: Account<'info, GameServerProfile>,
+
+    #[account(
+        mut,
+        seeds = [
+            PokerEscrow::SEED_PREFIX,
+            game_server.key().as_ref(),
+            poker_escrow.game_id.to_le_bytes().as_ref()
+        ],
+        bump = poker_escrow.bump,
+        has_one = game_server,
+    )]
+    pub poker_escrow: Account<'info, PokerEscrow>,
+}
+
+pub fn handler(ctx: Context<UpdateStacks>, stacks: Vec<StackUpdate>) -> Result<()> {
+    let poker_escrow = &mut ctx.accounts.poker_escrow;
+
+    // Stacks only change between hands of multi-hand games; single hands
+    // are always refunded by buy-in
+    require!(poker_escrow.status == GameStatus::Active, PokerError::GameNotActive);
+    require!(poker_escrow.game_type != GameType::SingleHand, PokerError::InvalidGameType);
+
+    poker_escrow.update_stacks(&stacks)?;
+    poker_escrow.touch(Clock::get()?.unix_timestamp);
+
+    msg!(
+        "Updated {} stacks in poker game {}, pot: {} lamports",
+        stacks.len(),
+        poker_escrow.game_id,
+        poker_escrow.total_pot
+    );
+
+    Ok(())
+}
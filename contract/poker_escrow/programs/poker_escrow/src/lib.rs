@@ -4,89 +4,510 @@ use anchor_lang::prelude::*;
 mod state;
 mod errors;
 mod instructions;
+mod utils;
+#[cfg(test)]
+mod test_fixtures;
 
 use instructions::*;
-use state::GameType;
+use state::{AccessProof, BackerTerms, GameType, LimitSettings, Payout, PointsFormula, SeatStatus, StackUpdate, JoinRules};
 
 declare_id!("2trpGNhySFn7mZysNXJMHsiQQb5Mp8LFU5sJCS35W6Qq");
 
-#[program]
-pub mod poker_escrow {
+// Anchor's generated IDL instructions call AccountInfo::realloc, deprecated
+// since solana-program 2.3. They are emitted next to the program module, so
+// the lint is allowed on this wrapper rather than the whole crate.
+#[allow(deprecated)]
+mod entry {
     use super::*;
 
-    /// Create a new poker game escrow with enhanced poker features
-    pub fn create_game(
-        ctx: Context<CreateGame>,
-        game_id: u64,
-        buy_in: u64,
-        min_players: u8,
-        max_players: u8,
-        game_type: GameType,
-        hand_identifier: String,
-    ) -> Result<()> {
-        instructions::create_game::handler(
-            ctx, 
-            game_id, 
-            buy_in, 
-            min_players, 
-            max_players, 
-            game_type, 
-            hand_identifier
-        )
-    }
+    #[program]
+    pub mod poker_escrow {
+        use super::*;
 
-    /// Join an existing poker game (unchanged from original)
-    pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
-        instructions::join_game::handler(ctx)
-    }
+        /// Initialize the global program config (program upgrade authority only)
+        pub fn initialize_config(
+            ctx: Context<InitializeConfig>,
+            treasury: Pubkey,
+            fee_bps: u16,
+            allowed_game_types: Vec<GameType>,
+            max_buy_in: u64,
+            min_server_bond: u64,
+            arbiter: Pubkey,
+        ) -> Result<()> {
+            instructions::initialize_config::handler(
+                ctx,
+                treasury,
+                fee_bps,
+                allowed_game_types,
+                max_buy_in,
+                min_server_bond,
+                arbiter,
+            )
+        }
 
-    /// Start a poker hand when minimum players reached
-    pub fn start_game(
-        ctx: Context<StartGame>,
-        hand_identifier: Option<String>,
-    ) -> Result<()> {
-        instructions::start_game::handler(ctx, hand_identifier)
-    }
+        /// Update program config settings (admin only). Fee and arbiter
+        /// changes are queued for `apply_config_change`.
+        pub fn update_config(
+            ctx: Context<UpdateConfig>,
+            treasury: Option<Pubkey>,
+            fee_bps: Option<u16>,
+            allowed_game_types: Option<Vec<GameType>>,
+            max_buy_in: Option<u64>,
+            min_server_bond: Option<u64>,
+            arbiter: Option<Pubkey>,
+        ) -> Result<()> {
+            instructions::update_config::handler(
+                ctx,
+                treasury,
+                fee_bps,
+                allowed_game_types,
+                max_buy_in,
+                min_server_bond,
+                arbiter,
+            )
+        }
 
-    /// Distribute pot to winner with hand result details
-    pub fn distribute_pot(
-        ctx: Context<DistributePot>,
-        amount: u64,
-        hand_rank: u8,
-        hand_description: String,
-    ) -> Result<()> {
-        instructions::distribute_pot::handler(ctx, amount, hand_rank, hand_description)
-    }
+        /// Apply a queued fee or arbiter change once its delay has passed
+        /// (anyone can crank)
+        pub fn apply_config_change(ctx: Context<ApplyConfigChange>) -> Result<()> {
+            instructions::apply_config_change::handler(ctx)
+        }
 
-    /// Emergency refund for abandoned games (unchanged from original)
-    pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
-        instructions::emergency_refund::handler(ctx)
-    }
+        /// Cancel a queued fee or arbiter change (guardian only)
+        pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+            instructions::cancel_config_change::handler(ctx)
+        }
 
-    /// Close completed Tournament/CashGame accounts
-    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
-        instructions::close_game::handler(ctx)
-    }
+        /// Hand the guardian role, which can pause the program and cancel
+        /// treasury withdrawals, to a new key (current guardian only)
+        pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+            instructions::set_guardian::handler(ctx, guardian)
+        }
+
+        /// Pause or unpause new games and payouts (admin or guardian)
+        pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+            instructions::set_paused::handler(ctx, paused)
+        }
+
+        /// Nominate a new admin (first step of the authority transfer)
+        pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+            instructions::transfer_admin::handler(ctx, new_admin)
+        }
+
+        /// Accept a pending admin nomination (second step of the authority transfer)
+        pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+            instructions::accept_admin::handler(ctx)
+        }
+
+        /// Register as a game server by locking a SOL bond
+        pub fn register_game_server(ctx: Context<RegisterGameServer>, bond: u64) -> Result<()> {
+            instructions::register_game_server::handler(ctx, bond)
+        }
+
+        /// Add SOL to a game server bond
+        pub fn top_up_bond(ctx: Context<TopUpBond>, amount: u64) -> Result<()> {
+            instructions::top_up_bond::handler(ctx, amount)
+        }
+
+        /// Slash a game server bond to compensate the players of one of its
+        /// games (admin or arbiter)
+        pub fn slash_game_server<'info>(
+            ctx: Context<'_, '_, 'info, 'info, SlashGameServer<'info>>,
+            amounts: Vec<u64>,
+        ) -> Result<()> {
+            instructions::slash_game_server::handler(ctx, amounts)
+        }
+
+        /// Rotate the game server's operational signer (owner only)
+        pub fn set_operator(ctx: Context<SetOperator>, new_operator: Pubkey) -> Result<()> {
+            instructions::set_operator::handler(ctx, new_operator)
+        }
+
+        /// Grant or revoke a delegate hot key for start_game/distribute_pot (owner only)
+        pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey, permissions: u8) -> Result<()> {
+            instructions::set_delegate::handler(ctx, delegate, permissions)
+        }
+
+        /// Create a season that new games score leaderboard points for (admin only)
+        pub fn create_season(
+            ctx: Context<CreateSeason>,
+            season_id: u32,
+            starts_at: i64,
+            ends_at: i64,
+            points: PointsFormula,
+            rake_share_bps: u16,
+            prize_bps: Vec<u16>,
+        ) -> Result<()> {
+            instructions::create_season::handler(ctx, season_id, starts_at, ends_at, points, rake_share_bps, prize_bps)
+        }
+
+        /// Sign up for a season's leaderboard
+        pub fn join_season(ctx: Context<JoinSeason>) -> Result<()> {
+            instructions::join_season::handler(ctx)
+        }
+
+        /// Close an ended season and pay its prize pool to the leaderboard (admin only)
+        pub fn close_season<'info>(ctx: Context<'_, '_, 'info, 'info, CloseSeason<'info>>) -> Result<()> {
+            instructions::close_season::handler(ctx)
+        }
+
+        /// Set responsible gaming limits for the signing player. Stricter limits
+        /// apply immediately, looser ones after a cooling-off period.
+        pub fn set_player_limits(ctx: Context<SetPlayerLimits>, settings: LimitSettings) -> Result<()> {
+            instructions::set_player_limits::handler(ctx, settings)
+        }
+
+        /// Issue or renew a compliance attestation for a player (attester signs)
+        pub fn issue_attestation(
+            ctx: Context<IssueAttestation>,
+            player: Pubkey,
+            flags: u8,
+            expires_at: i64,
+        ) -> Result<()> {
+            instructions::issue_attestation::handler(ctx, player, flags, expires_at)
+        }
+
+        /// Revoke a compliance attestation, returning its rent to the attester
+        pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+            instructions::revoke_attestation::handler(ctx)
+        }
+
+        /// Deposit SOL into the signing player's bankroll
+        pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+            instructions::deposit::handler(ctx, amount)
+        }
+
+        /// Authorize an ephemeral session key to act for the signing player
+        pub fn create_session(
+            ctx: Context<CreateSession>,
+            session_key: Pubkey,
+            permissions: u8,
+            spend_limit: u64,
+            expires_at: i64,
+        ) -> Result<()> {
+            instructions::create_session::handler(ctx, session_key, permissions, spend_limit, expires_at)
+        }
+
+        /// Revoke a session key, returning the session's rent to the player
+        pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+            instructions::revoke_session::handler(ctx)
+        }
+
+        /// Request a time-locked withdrawal from a player's bankroll to their
+        /// wallet (player or session key)
+        pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+            instructions::request_withdrawal::handler(ctx, amount)
+        }
+
+        /// Pay out a bankroll withdrawal once unlocked (anyone can crank)
+        pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+            instructions::execute_withdrawal::handler(ctx)
+        }
+
+        /// Cancel a pending bankroll withdrawal (player wallet only)
+        pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+            instructions::cancel_withdrawal::handler(ctx)
+        }
+
+        /// Create the protocol fee vault and route fees to it (admin only)
+        pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+            instructions::initialize_treasury::handler(ctx)
+        }
+
+        /// Request a time-locked withdrawal of protocol fees (admin only)
+        pub fn request_treasury_withdrawal(
+            ctx: Context<RequestTreasuryWithdrawal>,
+            destination: Pubkey,
+            amount: u64,
+        ) -> Result<()> {
+            instructions::request_treasury_withdrawal::handler(ctx, destination, amount)
+        }
+
+        /// Pay out a treasury withdrawal once unlocked (anyone can crank)
+        pub fn execute_treasury_withdrawal(ctx: Context<ExecuteTreasuryWithdrawal>) -> Result<()> {
+            instructions::execute_treasury_withdrawal::handler(ctx)
+        }
+
+        /// Cancel a pending treasury withdrawal (guardian only)
+        pub fn cancel_treasury_withdrawal(ctx: Context<CancelTreasuryWithdrawal>) -> Result<()> {
+            instructions::cancel_treasury_withdrawal::handler(ctx)
+        }
+
+        /// Offer a share of a pending game's action to backers, who together
+        /// fund the buy-in
+        pub fn create_backing(ctx: Context<CreateBacking>, backers: Vec<BackerTerms>) -> Result<()> {
+            instructions::create_backing::handler(ctx, backers)
+        }
+
+        /// Pay a backer's agreed part of the buy-in into a backing
+        pub fn fund_backing(ctx: Context<FundBacking>) -> Result<()> {
+            instructions::fund_backing::handler(ctx)
+        }
+
+        /// Split what a backing received between backers and player once the
+        /// game is over (anyone can crank), or call it off before joining
+        pub fn settle_backing<'info>(ctx: Context<'_, '_, 'info, 'info, SettleBacking<'info>>) -> Result<()> {
+            instructions::settle_backing::handler(ctx)
+        }
+
+        /// Create a new poker game escrow with enhanced poker features. Who
+        /// may join is fixed here, so nobody can sit down before it applies.
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_game(
+            ctx: Context<CreateGame>,
+            game_id: u64,
+            buy_in: u64,
+            min_players: u8,
+            max_players: u8,
+            game_type: GameType,
+            hand_identifier: String,
+            rules: JoinRules,
+        ) -> Result<()> {
+            instructions::create_game::handler(
+                ctx, 
+                game_id, 
+                buy_in, 
+                min_players, 
+                max_players, 
+                game_type, 
+                hand_identifier,
+                rules
+            )
+        }
+
+        /// Join an existing poker game, optionally choosing a seat. Private
+        /// tables need an allowlist proof, or an Ed25519 instruction in which
+        /// the invite issuer signs `PokerEscrow::invite_message`.
+        pub fn join_game(
+            ctx: Context<JoinGame>,
+            seat: Option<u8>,
+            access_proof: Option<AccessProof>,
+        ) -> Result<()> {
+            instructions::join_game::handler(ctx, seat, access_proof, BuyInSource::Wallet)
+        }
+
+        /// Join an existing poker game paying the buy-in from the player's
+        /// bankroll. Winnings and refunds are credited back to the bankroll.
+        pub fn join_game_from_bankroll(
+            ctx: Context<JoinGame>,
+            seat: Option<u8>,
+            access_proof: Option<AccessProof>,
+        ) -> Result<()> {
+            instructions::join_game::handler(ctx, seat, access_proof, BuyInSource::Bankroll)
+        }
+
+        /// Join an existing poker game paying the buy-in from a funded backing.
+        /// Winnings and refunds go to the backing until `settle_backing`.
+        pub fn join_game_backed(
+            ctx: Context<JoinGame>,
+            seat: Option<u8>,
+            access_proof: Option<AccessProof>,
+        ) -> Result<()> {
+            instructions::join_game::handler(ctx, seat, access_proof, BuyInSource::Backing)
+        }
+
+        /// Start a poker hand when minimum players reached and pick the dealer button
+        pub fn start_game(
+            ctx: Context<StartGame>,
+            hand_identifier: Option<String>,
+        ) -> Result<()> {
+            instructions::start_game::handler(ctx, hand_identifier)
+        }
+
+        /// Distribute pot to winner with hand result details
+        pub fn distribute_pot<'info>(
+            ctx: Context<'_, '_, 'info, 'info, DistributePot<'info>>,
+            amount: u64,
+            hand_rank: u8,
+            hand_description: String,
+        ) -> Result<()> {
+            instructions::distribute_pot::handler(ctx, amount, hand_rank, hand_description)
+        }
+
+        /// Set the challenge window and dispute bond for two-phase settlement
+        pub fn set_settlement_window(
+            ctx: Context<SetSettlementWindow>,
+            settlement_window: i64,
+            dispute_bond: u64,
+        ) -> Result<()> {
+            instructions::set_settlement_window::handler(ctx, settlement_window, dispute_bond)
+        }
+
+        /// Propose payouts for a hand, opening the challenge window
+        pub fn propose_settlement(
+            ctx: Context<ProposeSettlement>,
+            payouts: Vec<Payout>,
+            hand_rank: u8,
+            hand_description: String,
+        ) -> Result<()> {
+            instructions::propose_settlement::handler(ctx, payouts, hand_rank, hand_description)
+        }
+
+        /// Dispute a proposed settlement by staking a bond
+        pub fn dispute_settlement(ctx: Context<DisputeSettlement>) -> Result<()> {
+            instructions::dispute_settlement::handler(ctx)
+        }
+
+        /// Pay out an undisputed settlement after the challenge window
+        pub fn finalize_settlement<'info>(
+            ctx: Context<'_, '_, 'info, 'info, FinalizeSettlement<'info>>,
+        ) -> Result<()> {
+            instructions::finalize_settlement::handler(ctx)
+        }
+
+        /// Resolve a disputed settlement (admin or arbiter)
+        pub fn resolve_dispute<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+            corrected_payouts: Option<Vec<Payout>>,
+        ) -> Result<()> {
+            instructions::resolve_dispute::handler(ctx, corrected_payouts)
+        }
+
+        /// Abandon a game whose settlement went unpaid long past its window,
+        /// returning any dispute bond, so deposits can be refunded
+        pub fn expire_settlement(ctx: Context<ExpireSettlement>) -> Result<()> {
+            instructions::expire_settlement::handler(ctx)
+        }
+
+        /// Set how many attestations settle a hand without the server alone
+        pub fn set_attestation_threshold(
+            ctx: Context<SetAttestationThreshold>,
+            attestation_threshold: u8,
+        ) -> Result<()> {
+            instructions::set_attestation_threshold::handler(ctx, attestation_threshold)
+        }
+
+        /// Distribute pot on a payout list co-signed by a threshold of players
+        pub fn distribute_pot_attested<'info>(
+            ctx: Context<'_, '_, 'info, 'info, DistributePotAttested<'info>>,
+            payouts: Vec<Payout>,
+            hand_rank: u8,
+            hand_description: String,
+        ) -> Result<()> {
+            instructions::distribute_pot_attested::handler(ctx, payouts, hand_rank, hand_description)
+        }
+
+        /// Start settling a cash game from the latest fully signed off-chain state
+        pub fn settle_channel(
+            ctx: Context<SettleChannel>,
+            nonce: u64,
+            stacks: Vec<StackUpdate>,
+        ) -> Result<()> {
+            instructions::settle_channel::handler(ctx, nonce, stacks)
+        }
+
+        /// Replace a submitted channel state with a newer fully signed one
+        pub fn challenge_channel(
+            ctx: Context<ChallengeChannel>,
+            nonce: u64,
+            stacks: Vec<StackUpdate>,
+        ) -> Result<()> {
+            instructions::challenge_channel::handler(ctx, nonce, stacks)
+        }
+
+        /// Pay out the channel state once the challenge window passes
+        pub fn finalize_channel<'info>(
+            ctx: Context<'_, '_, 'info, 'info, FinalizeChannel<'info>>,
+        ) -> Result<()> {
+            instructions::finalize_channel::handler(ctx)
+        }
+
+        /// Require M-of-N operator signatures for large payouts and closing
+        pub fn set_payout_signers(
+            ctx: Context<SetPayoutSigners>,
+            payout_signers: Vec<Pubkey>,
+            payout_threshold: u8,
+            large_payout_amount: u64,
+        ) -> Result<()> {
+            instructions::set_payout_signers::handler(ctx, payout_signers, payout_threshold, large_payout_amount)
+        }
+
+        /// Emergency refund for abandoned, expired, or idle games
+        pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
+            instructions::emergency_refund::handler(ctx)
+        }
+
+        /// Close completed Tournament/CashGame accounts
+        pub fn close_game<'info>(ctx: Context<'_, '_, 'info, 'info, CloseGame<'info>>) -> Result<()> {
+            instructions::close_game::handler(ctx)
+        }
+
+        /// Cancel a pending game, refunding players passed as remaining accounts
+        pub fn cancel_game<'info>(ctx: Context<'_, '_, 'info, 'info, CancelGame<'info>>) -> Result<()> {
+            instructions::cancel_game::handler(ctx)
+        }
+
+        /// Permissionless refund of abandoned, expired, or idle games to recorded players
+        pub fn crank_refund<'info>(ctx: Context<'_, '_, 'info, 'info, CrankRefund<'info>>) -> Result<()> {
+            instructions::crank_refund::handler(ctx)
+        }
+
+        /// Mark a player as active, sitting out, or eliminated
+        pub fn set_seat_status(
+            ctx: Context<SetSeatStatus>,
+            player: Pubkey,
+            status: SeatStatus,
+        ) -> Result<()> {
+            instructions::set_seat_status::handler(ctx, player, status)
+        }
+
+        /// Set the share of each buy-in held as a bounty in a knockout
+        /// tournament, and whether it is progressive. Bounties are carved out
+        /// of buy-ins as they arrive, so this must precede the first join.
+        pub fn set_bounty(ctx: Context<SetBounty>, bounty_bps: u16, progressive: bool) -> Result<()> {
+            instructions::set_bounty::handler(ctx, bounty_bps, progressive)
+        }
+
+        /// Knock a player out of a knockout tournament, paying their bounty (or
+        /// half, if progressive) to the eliminator
+        pub fn eliminate_player(
+            ctx: Context<EliminatePlayer>,
+            player: Pubkey,
+            eliminator: Pubkey,
+        ) -> Result<()> {
+            instructions::eliminate_player::handler(ctx, player, eliminator)
+        }
+
+        /// Release the bounties left on standing players into the prize pool
+        /// once a knockout tournament is down to its paid places
+        pub fn release_bounties(ctx: Context<ReleaseBounties>) -> Result<()> {
+            instructions::release_bounties::handler(ctx)
+        }
+
+        /// Report player stacks so idle refunds follow the latest chip counts
+        pub fn update_stacks(ctx: Context<UpdateStacks>, stacks: Vec<StackUpdate>) -> Result<()> {
+            instructions::update_stacks::handler(ctx, stacks)
+        }
+
+        /// Set how long an active game may go without server actions before refunds open
+        pub fn set_idle_timeout(ctx: Context<SetIdleTimeout>, idle_timeout: i64) -> Result<()> {
+            instructions::set_idle_timeout::handler(ctx, idle_timeout)
+        }
 
-    /// Get game information (view function)
-    pub fn get_game_info(ctx: Context<GetGameInfo>) -> Result<GameInfo> {
-        let poker_escrow = &ctx.accounts.poker_escrow;
+        /// Get game information (view function)
+        pub fn get_game_info(ctx: Context<GetGameInfo>) -> Result<GameInfo> {
+            let poker_escrow = &ctx.accounts.poker_escrow;
         
-        Ok(GameInfo {
-            game_id: poker_escrow.game_id,
-            game_type: poker_escrow.game_type.clone(),
-            status: poker_escrow.status.clone(),
-            current_players: poker_escrow.current_players,
-            max_players: poker_escrow.max_players,
-            total_pot: poker_escrow.total_pot,
-            buy_in: poker_escrow.buy_in,
-            hand_identifier: poker_escrow.hand_identifier.clone(),
-            hand_number: poker_escrow.hand_number,
-            dealer_position: poker_escrow.dealer_position,
-            total_hands_played: poker_escrow.total_hands_played,
-        })
+            Ok(GameInfo {
+                game_id: poker_escrow.game_id,
+                game_type: poker_escrow.game_type.clone(),
+                status: poker_escrow.status.clone(),
+                current_players: poker_escrow.current_players,
+                max_players: poker_escrow.max_players,
+                total_pot: poker_escrow.total_pot,
+                buy_in: poker_escrow.buy_in,
+                fee_bps: poker_escrow.fee_bps,
+                hand_identifier: poker_escrow.hand_identifier.clone(),
+                hand_number: poker_escrow.hand_number,
+                dealer_position: poker_escrow.dealer_position,
+                dealer_seed: poker_escrow.dealer_seed,
+                dealer_seed_slot: poker_escrow.dealer_seed_slot,
+                total_hands_played: poker_escrow.total_hands_played,
+            })
+        }
     }
 }
+pub use entry::*;
 
 #[derive(Accounts)]
 pub struct GetGameInfo<'info> {
@@ -102,8 +523,11 @@ pub struct GameInfo {
     pub max_players: u8,
     pub total_pot: u64,
     pub buy_in: u64,
+    pub fee_bps: u16,
     pub hand_identifier: String,
     pub hand_number: u32,
     pub dealer_position: u8,
+    pub dealer_seed: [u8; 32],
+    pub dealer_seed_slot: u64,
     pub total_hands_played: u32,
 }
\ No newline at end of file
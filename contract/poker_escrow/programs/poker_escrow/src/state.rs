@@ -1,5 +1,6 @@
 // programs/poker_escrow/src/state.rs
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum GameStatus {
@@ -7,6 +8,7 @@ pub enum GameStatus {
     Active,      // Game in progress
     Completed,   // Game finished, payouts done
     Abandoned,   // Game abandoned, refunds available
+    Settling,    // Settlement or channel state in its challenge window
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
@@ -14,6 +16,21 @@ pub enum GameType {
     SingleHand,     // MVP: One poker hand per contract
     Tournament,     // Future: Multiple hands
     CashGame,       // Future: Continuous play
+    KnockoutTournament, // Tournament with a bounty on every player
+}
+
+impl GameType {
+    /// Formats that eliminate players until one is left
+    pub fn is_tournament(&self) -> bool {
+        matches!(self, GameType::Tournament | GameType::KnockoutTournament)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum SeatStatus {
+    Active,      // Dealt into hands
+    SittingOut,  // Keeps the seat but skipped for the button
+    Eliminated,  // Busted out (Tournament), never dealt in again
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -21,6 +38,62 @@ pub struct PlayerDeposit {
     pub player: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
+    pub seat: u8,            // Table seat (0..max_players)
+    pub status: SeatStatus,
+    pub stack: u64,          // Last stack reported by the game server
+    pub payout_to: Pubkey,   // Wallet, or the bankroll or backing that paid the buy-in
+    pub bounty: u64,         // Held for whoever knocks them out (KnockoutTournament)
+    pub winnings: u64,       // Paid out to them in this game, before fees
+    pub eliminated: u8,      // Elimination order (1 = first out), 0 while standing
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct StackUpdate {
+    pub player: Pubkey,
+    pub stack: u64,
+}
+
+/// Who may join a table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum TableAccess {
+    Public,
+    Allowlist { merkle_root: [u8; 32] },   // Merkle root of allowed player keys
+    Invite { issuer: Pubkey },             // Signs each invited player's key
+}
+
+/// Who may join a table, fixed when it is created
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct JoinRules {
+    pub access: TableAccess,
+    pub token_gate: Option<TokenGate>,
+    pub required_attester: Option<Pubkey>,   // Compliance attester joiners need
+    pub required_attestation_flags: u8,      // Checks their attestation must cover
+}
+
+/// Token holding required to join a table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum TokenGate {
+    Mint { mint: Pubkey, min_amount: u64 },   // Minimum balance of an SPL mint
+    Collection { collection: Pubkey },        // An NFT from a verified collection
+}
+
+/// What a player presents to join an allowlisted table. Invites are
+/// Ed25519 signatures in the same transaction instead.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AccessProof {
+    Merkle(Vec<[u8; 32]>),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Payout {
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum SettlementStatus {
+    Proposed,    // Challenge window open
+    Disputed,    // Waiting for arbiter resolution
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -38,7 +111,7 @@ pub struct PokerEscrow {
     /// Unique game identifier
     pub game_id: u64,
     
-    /// Game server authorized to trigger payouts
+    /// Game server profile whose operators may trigger payouts
     pub game_server: Pubkey,
     
     /// Required buy-in amount per player (in lamports)
@@ -58,6 +131,9 @@ pub struct PokerEscrow {
     /// Game type for different poker formats
     pub game_type: GameType,
     
+    /// Protocol fee (basis points) taken from payouts, fixed at creation
+    pub fee_bps: u16,
+    
     /// Hand identifier for tracking (hash or round number)
     #[max_len(64)]
     pub hand_identifier: String,
@@ -75,11 +151,53 @@ pub struct PokerEscrow {
     pub started_at: Option<i64>,
     pub completed_at: Option<i64>,
     
+    /// Last game server action, and how long the server may stay silent
+    /// on an active game before players can claim refunds
+    pub last_activity_at: i64,
+    pub idle_timeout: i64,
+    
+    /// Two-phase settlement: payouts wait this long for disputes (0 = immediate)
+    pub settlement_window: i64,
+    pub dispute_bond: u64,      // Lamports a player stakes to dispute
+    
+    /// Attestations needed to settle without the server alone (0 = disabled).
+    /// Seated players count one each, a co-signing game server counts as one.
+    pub attestation_threshold: u8,
+    
+    /// Optional M-of-N operator co-signing for large payouts and closing
+    #[max_len(5)]
+    pub payout_signers: Vec<Pubkey>,
+    pub payout_threshold: u8,       // Signatures required (0 = disabled)
+    pub large_payout_amount: u64,   // Lamports at which co-signing kicks in
+    pub paid_out: u64,              // Lamports paid out of the pot so far
+    
+    /// Season this game's payouts score points for, fixed at creation
+    pub season: Option<Pubkey>,
+    
+    /// Who may join: anyone, an allowlist, or invite holders
+    pub access: TableAccess,
+    
+    /// Token joiners must hold, if any
+    pub token_gate: Option<TokenGate>,
+    
+    /// Attester whose compliance attestation joiners need, if any
+    pub required_attester: Option<Pubkey>,
+    pub required_attestation_flags: u8,
+    
+    /// Share of each buy-in held as a bounty (KnockoutTournament, basis
+    /// points), and whether eliminators only collect half of it
+    pub bounty_bps: u16,
+    pub progressive_bounty: bool,
+    
     /// Poker-specific fields
     pub dealer_position: u8,        // Dealer button position
     pub hand_number: u32,           // Current hand number (for multi-hand games)
     pub total_hands_played: u32,    // Total hands completed
     
+    /// Seed used to pick the initial dealer button (recomputable by anyone)
+    pub dealer_seed: [u8; 32],
+    pub dealer_seed_slot: u64,      // Slot whose hash was mixed into the seed
+    
     /// PDA bump
     pub bump: u8,
 }
@@ -87,6 +205,29 @@ pub struct PokerEscrow {
 impl PokerEscrow {
     pub const SEED_PREFIX: &'static [u8] = b"poker_game";
     
+    pub const MAX_PAYOUT_SIGNERS: usize = 5;
+    
+    /// Enough for allowlists of up to 65536 players
+    pub const MAX_MERKLE_PROOF_LEN: usize = 16;
+    
+    /// Domain separator for signed invites
+    pub const INVITE_DOMAIN: &'static [u8] = b"poker_escrow:invite";
+    
+    /// Knockout tournaments hold half of each buy-in as bounties by default
+    pub const DEFAULT_BOUNTY_BPS: u16 = 5_000;
+    
+    /// Pending games can be refunded after 24 hours
+    pub const REFUND_TIMEOUT: i64 = 24 * 60 * 60;
+    
+    /// Active games can be refunded after this long without a server action
+    pub const DEFAULT_IDLE_TIMEOUT: i64 = 24 * 60 * 60;
+    pub const MIN_IDLE_TIMEOUT: i64 = 60 * 60;
+    pub const MAX_IDLE_TIMEOUT: i64 = 7 * 24 * 60 * 60;
+    
+    /// Bounds on the settlement challenge window
+    pub const MIN_SETTLEMENT_WINDOW: i64 = 60;
+    pub const MAX_SETTLEMENT_WINDOW: i64 = 7 * 24 * 60 * 60;
+    
     /// Calculate total space needed (updated with new fields)
     pub const MAX_SIZE: usize = 8 + // discriminator
         8 + // game_id
@@ -98,15 +239,34 @@ impl PokerEscrow {
         1 + // current_players
         1 + // status
         1 + // game_type
+        2 + // fee_bps
         4 + 64 + // hand_identifier (String with max 64 chars)
-        4 + (10 * (32 + 8 + 8)) + // players vec (max 10)
+        4 + (10 * (32 + 8 + 8 + 1 + 1 + 8 + 32 + 8 + 8 + 1)) + // players vec (max 10)
         4 + (10 * (32 + 1 + 4 + 32 + 8)) + // hand_results vec (max 10 * HandResult size)
         8 + // created_at
         9 + // started_at (Option<i64>)
         9 + // completed_at (Option<i64>)
+        8 + // last_activity_at
+        8 + // idle_timeout
+        8 + // settlement_window
+        8 + // dispute_bond
+        1 + // attestation_threshold
+        4 + (5 * 32) + // payout_signers (max 5)
+        1 + // payout_threshold
+        8 + // large_payout_amount
+        8 + // paid_out
+        33 + // season (Option<Pubkey>)
+        1 + 32 + // access (TableAccess)
+        1 + 1 + 32 + 8 + // token_gate (Option<TokenGate>)
+        33 + // required_attester (Option<Pubkey>)
+        1 + // required_attestation_flags
+        2 + // bounty_bps
+        1 + // progressive_bounty
         1 + // dealer_position
         4 + // hand_number
         4 + // total_hands_played
+        32 + // dealer_seed
+        8 + // dealer_seed_slot
         1; // bump
 
     /// Check if player already joined
@@ -120,21 +280,130 @@ impl PokerEscrow {
         self.current_players >= self.min_players
     }
 
+    /// Check if table settings can still change (nobody has joined yet).
+    /// Players accept a table's terms by depositing, so every setter locks
+    /// once the first buy-in is in.
+    pub fn can_configure(&self) -> bool {
+        self.status == GameStatus::Pending && self.current_players == 0
+    }
+
+    /// Check if deposits can be refunded without the game server: the game
+    /// was abandoned, never started within the timeout, or the server went idle
+    pub fn is_refundable(&self, now: i64) -> bool {
+        match self.status {
+            GameStatus::Pending => now - self.created_at > Self::REFUND_TIMEOUT,
+            GameStatus::Active => now - self.last_activity_at > self.idle_timeout,
+            GameStatus::Abandoned => true,
+            GameStatus::Completed | GameStatus::Settling => false,
+        }
+    }
+
+    /// Message an invite issuer signs (via the Ed25519 precompile) to let
+    /// `player` join. Naming the player keeps a seen invite from being reused.
+    pub fn invite_message(poker_escrow: &Pubkey, player: &Pubkey) -> Vec<u8> {
+        [Self::INVITE_DOMAIN, poker_escrow.as_ref(), player.as_ref()].concat()
+    }
+
+    /// Check if a player may join this table. Allowlist leaves are
+    /// `hash(player)`, and each proof step hashes the sorted pair of nodes.
+    /// Invite tables need the issuer among `invited_by`, the keys that
+    /// signed this player's invite message.
+    pub fn may_join(&self, player: &Pubkey, proof: Option<&AccessProof>, invited_by: &[Pubkey]) -> bool {
+        match (&self.access, proof) {
+            (TableAccess::Public, _) => true,
+            (TableAccess::Allowlist { merkle_root }, Some(AccessProof::Merkle(path))) => {
+                if path.len() > Self::MAX_MERKLE_PROOF_LEN {
+                    return false;
+                }
+                let leaf = hashv(&[player.as_ref()]).to_bytes();
+                let root = path.iter().fold(leaf, |node, sibling| {
+                    if node <= *sibling {
+                        hashv(&[&node, sibling]).to_bytes()
+                    } else {
+                        hashv(&[sibling, &node]).to_bytes()
+                    }
+                });
+                root == *merkle_root
+            },
+            (TableAccess::Invite { issuer }, _) => invited_by.contains(issuer),
+            _ => false,
+        }
+    }
+
+    /// Check if moving `amount` lamports out needs operator co-signatures.
+    /// Counts everything already paid out, so a large pot can't be moved
+    /// in payouts that each stay under the limit.
+    pub fn requires_cosigners(&self, amount: u64) -> bool {
+        self.payout_threshold > 0 &&
+            self.paid_out.saturating_add(amount) >= self.large_payout_amount
+    }
+
+    /// Count distinct stored payout signers among `signers`
+    pub fn count_payout_signers(&self, signers: &[Pubkey]) -> usize {
+        self.payout_signers
+            .iter()
+            .filter(|key| signers.contains(key))
+            .count()
+    }
+
+    /// Record a game server action, resetting the idle refund clock
+    pub fn touch(&mut self, now: i64) {
+        self.last_activity_at = now;
+    }
+
     /// Check if game is full
     pub fn is_full(&self) -> bool {
         self.current_players >= self.max_players
     }
 
-    /// Add player to game
-    pub fn add_player(&mut self, player: Pubkey, amount: u64) -> Result<()> {
+    /// Check if a seat is occupied
+    pub fn is_seat_taken(&self, seat: u8) -> bool {
+        self.players.iter().any(|p| p.seat == seat)
+    }
+
+    /// Lowest unoccupied seat, if any
+    pub fn first_free_seat(&self) -> Option<u8> {
+        (0..self.max_players).find(|seat| !self.is_seat_taken(*seat))
+    }
+
+    /// Add player to game, in the requested seat or the first free one
+    pub fn add_player(
+        &mut self,
+        player: Pubkey,
+        amount: u64,
+        seat: Option<u8>,
+        payout_to: Pubkey,
+    ) -> Result<()> {
         require!(!self.is_full(), crate::errors::PokerError::GameFull);
         require!(!self.has_player(&player), crate::errors::PokerError::PlayerAlreadyJoined);
         require!(amount == self.buy_in, crate::errors::PokerError::IncorrectBuyIn);
 
+        let seat = match seat {
+            Some(seat) => {
+                require!(seat < self.max_players, crate::errors::PokerError::InvalidSeat);
+                require!(!self.is_seat_taken(seat), crate::errors::PokerError::SeatTaken);
+                seat
+            }
+            None => self.first_free_seat().ok_or(crate::errors::PokerError::GameFull)?,
+        };
+
+        let bounty = if self.game_type == GameType::KnockoutTournament {
+            ProgramConfig::fee_for(amount, self.bounty_bps)?
+        } else {
+            0
+        };
+
         self.players.push(PlayerDeposit {
             player,
             amount,
             timestamp: Clock::get()?.unix_timestamp,
+            seat,
+            status: SeatStatus::Active,
+            stack: amount,
+            payout_to,
+            bounty,
+            winnings: 0,
+            eliminated: 0,
         });
         
         self.current_players += 1;
@@ -143,10 +412,117 @@ impl PokerEscrow {
         Ok(())
     }
 
+    /// Part of the pot that can be paid as prizes: everything but the
+    /// bounties still on players' heads
+    pub fn prize_pool(&self) -> u64 {
+        let bounties: u64 = self.players.iter().map(|p| p.bounty).sum();
+        self.total_pot.saturating_sub(bounties)
+    }
+
+    /// Eliminate `player`, releasing their bounty into the prize pool.
+    /// When one player is left standing their own bounty is released too.
+    /// Returns the released bounty.
+    pub fn knock_out(&mut self, player: &Pubkey) -> Result<u64> {
+        self.set_seat_status(player, SeatStatus::Eliminated)?;
+        let deposit = self.players
+            .iter_mut()
+            .find(|p| p.player == *player)
+            .ok_or(crate::errors::PokerError::PlayerNotInGame)?;
+        let bounty = std::mem::take(&mut deposit.bounty);
+
+        let mut standing = self.players.iter_mut().filter(|p| p.status != SeatStatus::Eliminated);
+        if let (Some(last), None) = (standing.next(), standing.next()) {
+            last.bounty = 0;
+        }
+
+        Ok(bounty)
+    }
+
+    /// Release every bounty still held into the prize pool.
+    /// Returns the total released.
+    pub fn release_bounties(&mut self) -> u64 {
+        self.players
+            .iter_mut()
+            .map(|p| std::mem::take(&mut p.bounty))
+            .sum()
+    }
+
+    /// Account a player's winnings and refunds are paid to
+    pub fn payout_address(&self, player: &Pubkey) -> Pubkey {
+        self.players
+            .iter()
+            .find(|p| p.player == *player)
+            .map(|p| p.payout_to)
+            .unwrap_or(*player)
+    }
+
+    /// Player's share of the pot, proportional to their last known stack.
+    /// Before any stack report this is simply their buy-in.
+    pub fn refund_share(&self, player: &Pubkey) -> Result<u64> {
+        let stack = self.players
+            .iter()
+            .find(|p| p.player == *player)
+            .ok_or(crate::errors::PokerError::PlayerNotInGame)?
+            .stack;
+
+        // Last player out takes whatever is left, including rounding dust
+        if self.players.len() == 1 {
+            return Ok(self.total_pot);
+        }
+
+        let total_stacks: u128 = self.players.iter().map(|p| p.stack as u128).sum();
+        if total_stacks == 0 {
+            return Ok(0);
+        }
+
+        let share = (stack as u128)
+            .checked_mul(self.total_pot as u128)
+            .ok_or(crate::errors::PokerError::MathOverflow)?
+            / total_stacks;
+        u64::try_from(share).map_err(|_| crate::errors::PokerError::MathOverflow.into())
+    }
+
+    /// Record stacks reported by the game server, which must cover the pot
+    pub fn update_stacks(&mut self, stacks: &[StackUpdate]) -> Result<()> {
+        let mut total: u64 = 0;
+        for update in stacks {
+            let deposit = self.players
+                .iter_mut()
+                .find(|p| p.player == update.player)
+                .ok_or(crate::errors::PokerError::PlayerNotInGame)?;
+            deposit.stack = update.stack;
+        }
+        for deposit in self.players.iter() {
+            total = total
+                .checked_add(deposit.stack)
+                .ok_or(crate::errors::PokerError::MathOverflow)?;
+        }
+        require!(total == self.total_pot, crate::errors::PokerError::PayoutMismatch);
+        Ok(())
+    }
+
+    /// Remove player from game, returning their share of the pot for refund
+    pub fn remove_player(&mut self, player: &Pubkey) -> Result<u64> {
+        let refund_amount = self.refund_share(player)?;
+        let index = self.players
+            .iter()
+            .position(|p| p.player == *player)
+            .ok_or(crate::errors::PokerError::PlayerNotInGame)?;
+        self.players.remove(index);
+
+        self.current_players -= 1;
+        self.total_pot = self.total_pot
+            .checked_sub(refund_amount)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+
+        Ok(refund_amount)
+    }
+
     /// Set hand result for transparency
     pub fn add_hand_result(&mut self, winner: Pubkey, hand_rank: u8, hand_description: String, amount: u64) -> Result<()> {
         // Validate hand rank is within valid poker range
         require!(hand_rank <= 9, crate::errors::PokerError::InvalidHandResult);
+        require!(hand_description.len() <= 32, crate::errors::PokerError::InvalidHandResult);
         
         // Only keep last 10 results to manage space
         if self.hand_results.len() >= 10 {
@@ -174,6 +550,32 @@ impl PokerEscrow {
         Ok(())
     }
 
+    /// Check if paying `amount` more out of the pot ends a rated game. Single
+    /// hands end with their one payout, tournaments once the pot is paid out.
+    pub fn completes_rated_game(&self, amount: u64) -> bool {
+        match self.game_type {
+            GameType::SingleHand => true,
+            GameType::Tournament | GameType::KnockoutTournament => self.total_pot == amount,
+            GameType::CashGame => false,
+        }
+    }
+
+    /// Where a player placed in a completed game, higher is better.
+    /// Tournament players place in elimination order, with those still
+    /// standing above everyone eliminated; ties among them, and single
+    /// hands, go by what each was paid.
+    pub fn placement_score(&self, deposit: &PlayerDeposit) -> u64 {
+        match self.game_type {
+            GameType::Tournament | GameType::KnockoutTournament if deposit.eliminated > 0 => {
+                deposit.eliminated as u64
+            }
+            GameType::Tournament | GameType::KnockoutTournament => {
+                (self.max_players as u64 + 1).saturating_add(deposit.winnings)
+            }
+            GameType::SingleHand | GameType::CashGame => deposit.winnings,
+        }
+    }
+
     /// Complete current hand
     pub fn complete_hand(&mut self) -> Result<()> {
         self.total_hands_played += 1;
@@ -187,20 +589,1294 @@ impl PokerEscrow {
         Ok(())
     }
 
+    /// Derive the dealer seed from a recent slot hash and the seated players.
+    /// Anyone can recompute it from `dealer_seed_slot` and the player list.
+    pub fn compute_dealer_seed(&self, slot_hash: &[u8; 32]) -> [u8; 32] {
+        let game_id = self.game_id.to_le_bytes();
+        let mut inputs: Vec<&[u8]> = vec![slot_hash.as_ref(), game_id.as_ref()];
+        inputs.extend(self.players.iter().map(|p| p.player.as_ref()));
+        hashv(&inputs).to_bytes()
+    }
+
+    /// Map a dealer seed onto the seat of one of the active players
+    pub fn dealer_from_seed(&self, seed: &[u8; 32]) -> u8 {
+        let mut active_seats: Vec<u8> = self.players
+            .iter()
+            .filter(|p| p.status == SeatStatus::Active)
+            .map(|p| p.seat)
+            .collect();
+        if active_seats.is_empty() {
+            return 0;
+        }
+        active_seats.sort_unstable();
+
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&seed[..8]);
+        active_seats[(u64::from_le_bytes(value) % active_seats.len() as u64) as usize]
+    }
+
+    /// Check if a seat holds a player who can take the button
+    pub fn is_seat_active(&self, seat: u8) -> bool {
+        self.players
+            .iter()
+            .any(|p| p.seat == seat && p.status == SeatStatus::Active)
+    }
+
     /// Rotate dealer button (for multi-hand games)
     pub fn rotate_dealer(&mut self) -> Result<()> {
-        if self.current_players > 0 {
-            self.dealer_position = (self.dealer_position + 1) % self.current_players;
-        }
+        self.dealer_position = self.get_next_dealer();
         Ok(())
     }
 
-    /// Get next dealer position
+    /// Get next dealer position, skipping empty, sitting-out and eliminated seats
     pub fn get_next_dealer(&self) -> u8 {
-        if self.current_players > 0 {
-            (self.dealer_position + 1) % self.current_players
+        (1..=self.max_players)
+            .map(|offset| ((self.dealer_position as u16 + offset as u16) % self.max_players as u16) as u8)
+            .find(|seat| self.is_seat_active(*seat))
+            .unwrap_or(self.dealer_position)
+    }
+
+    /// Update a player's seat status
+    pub fn set_seat_status(&mut self, player: &Pubkey, status: SeatStatus) -> Result<()> {
+        let deposit = self.players
+            .iter_mut()
+            .find(|p| p.player == *player)
+            .ok_or(crate::errors::PokerError::PlayerNotInGame)?;
+        require!(deposit.status != SeatStatus::Eliminated, crate::errors::PokerError::PlayerEliminated);
+        deposit.status = status;
+
+        // Elimination order places tournament players for ratings
+        if status == SeatStatus::Eliminated {
+            let eliminated = self.players
+                .iter()
+                .filter(|p| p.status == SeatStatus::Eliminated)
+                .count();
+            let deposit = self.players
+                .iter_mut()
+                .find(|p| p.player == *player)
+                .ok_or(crate::errors::PokerError::PlayerNotInGame)?;
+            deposit.eliminated = eliminated as u8;
+        }
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    /// Protocol admin, allowed to update this config
+    pub admin: Pubkey,
+    
+    /// Admin nominated by `transfer_admin`, must accept to take over
+    pub pending_admin: Option<Pubkey>,
+    
+    /// Account receiving protocol fees
+    pub treasury: Pubkey,
+    
+    /// Protocol fee on payouts (basis points)
+    pub fee_bps: u16,
+    
+    /// Game formats servers are allowed to create
+    #[max_len(8)]
+    pub allowed_game_types: Vec<GameType>,
+    
+    /// Maximum buy-in per player (in lamports)
+    pub max_buy_in: u64,
+    
+    /// Bond a game server must lock to register (in lamports)
+    pub min_server_bond: u64,
+    
+    /// Key allowed to slash game server bonds, alongside the admin
+    pub arbiter: Pubkey,
+    
+    /// Key allowed to pause the program, alongside the admin
+    pub guardian: Pubkey,
+    
+    /// Season new games are entered into
+    pub current_season: Option<Pubkey>,
+    
+    /// Circuit breaker: blocks new games and payouts, refunds stay open
+    pub paused: bool,
+    
+    /// Fee or arbiter change waiting out `CHANGE_DELAY`
+    pub pending_change: Option<ConfigChange>,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Fee and arbiter update queued by `update_config`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ConfigChange {
+    pub fee_bps: Option<u16>,
+    pub arbiter: Option<Pubkey>,
+    pub applies_at: i64,
+}
+
+impl ProgramConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Fees above 10% are rejected
+    pub const MAX_FEE_BPS: u16 = 1_000;
+    pub const BPS_DENOMINATOR: u64 = 10_000;
+    
+    /// The fee and the arbiter move money, so changing them gives the
+    /// guardian the same window to cancel as a treasury withdrawal
+    pub const CHANGE_DELAY: i64 = Treasury::WITHDRAWAL_DELAY;
+
+    /// Check if servers may create games of this type
+    pub fn allows_game_type(&self, game_type: &GameType) -> bool {
+        self.allowed_game_types.contains(game_type)
+    }
+
+    /// Validate fee and game type settings
+    pub fn validate(&self) -> Result<()> {
+        require!(self.fee_bps <= Self::MAX_FEE_BPS, crate::errors::PokerError::InvalidFee);
+        require!(self.max_buy_in > 0, crate::errors::PokerError::IncorrectBuyIn);
+        require!(
+            self.allowed_game_types.len() <= 8,
+            crate::errors::PokerError::InvalidGameType
+        );
+        Ok(())
+    }
+
+    /// Check if a key may rule on disputes
+    pub fn is_arbiter(&self, key: &Pubkey) -> bool {
+        *key == self.admin || *key == self.arbiter
+    }
+
+    /// Check if a key may pause or unpause the program
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        *key == self.admin || *key == self.guardian
+    }
+
+    /// Protocol fee owed on a payout
+    pub fn fee_for(amount: u64, fee_bps: u16) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(fee_bps as u128)
+            .ok_or(crate::errors::PokerError::MathOverflow)?
+            / Self::BPS_DENOMINATOR as u128;
+        u64::try_from(fee).map_err(|_| crate::errors::PokerError::MathOverflow.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PointsFormula {
+    pub per_hand_won: u64,
+    pub per_sol_won: u64,           // Scaled by lamports won after fees
+    pub tournament_win: u64,        // Bonus for the payout that ends a tournament
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SeasonStanding {
+    pub player: Pubkey,
+    pub points: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Delegate {
+    pub key: Pubkey,
+    pub permissions: u8,     // GameServerProfile::DELEGATE_* flags
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GameServerProfile {
+    /// Key the profile was registered with. Only used as the PDA seed, so
+    /// escrows stay reachable however the signing keys rotate.
+    pub server_id: Pubkey,
+    
+    /// Owner: manages keys and can do anything the operator can
+    pub authority: Pubkey,
+    
+    /// Operational signer for all game server actions
+    pub operator: Pubkey,
+    
+    /// Hot keys limited to the actions in their permission flags
+    #[max_len(5)]
+    pub delegates: Vec<Delegate>,
+    
+    /// Bond held in this account (in lamports, on top of rent)
+    pub bond: u64,
+    
+    /// Track record shown to players
+    pub games_hosted: u64,
+    pub disputes_lost: u32,
+    pub total_slashed: u64,
+    
+    pub registered_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl GameServerProfile {
+    pub const SEED_PREFIX: &'static [u8] = b"game_server";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    pub const MAX_DELEGATES: usize = 5;
+    
+    /// Delegate permission flags
+    pub const DELEGATE_START_GAME: u8 = 1 << 0;
+    pub const DELEGATE_DISTRIBUTE_POT: u8 = 1 << 1;
+    pub const DELEGATE_ALL: u8 = Self::DELEGATE_START_GAME | Self::DELEGATE_DISTRIBUTE_POT;
+
+    /// Check if a key may perform every game server action
+    pub fn can_operate(&self, key: &Pubkey) -> bool {
+        *key == self.authority || *key == self.operator
+    }
+
+    /// Check if a key may perform an action a delegate can be granted
+    pub fn can_perform(&self, key: &Pubkey, permission: u8) -> bool {
+        self.can_operate(key) ||
+        self.delegates
+            .iter()
+            .any(|d| d.key == *key && d.permissions & permission == permission)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    /// Wallet these statistics belong to
+    pub player: Pubkey,
+    
+    /// Lifetime statistics (amounts in lamports)
+    pub games_played: u64,
+    pub hands_won: u64,
+    pub total_buy_ins: u64,
+    pub total_winnings: u64,
+    pub total_refunded: u64,
+    pub biggest_pot: u64,
+    
+    pub created_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    pub const SEED_PREFIX: &'static [u8] = b"player_profile";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+
+    /// Record a buy-in into a new game
+    pub fn record_join(&mut self, buy_in: u64) -> Result<()> {
+        self.games_played = self.games_played
+            .checked_add(1)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        self.total_buy_ins = self.total_buy_ins
+            .checked_add(buy_in)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a won pot, `received` being the amount after fees
+    pub fn record_win(&mut self, pot: u64, received: u64) -> Result<()> {
+        self.hands_won = self.hands_won
+            .checked_add(1)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        self.total_winnings = self.total_winnings
+            .checked_add(received)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        self.biggest_pot = self.biggest_pot.max(pot);
+        Ok(())
+    }
+
+    /// Record a deposit returned by a refund
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.total_refunded = self.total_refunded
+            .checked_add(amount)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerRating {
+    /// Wallet this rating belongs to
+    pub player: Pubkey,
+    
+    /// Elo rating, updated when a rated game completes
+    pub rating: u32,
+    pub peak_rating: u32,
+    pub games_rated: u32,
+    pub last_rated_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PlayerRating {
+    pub const SEED_PREFIX: &'static [u8] = b"rating";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    pub const INITIAL_RATING: u32 = 1_200;
+    pub const MIN_RATING: u32 = 100;
+    pub const K_FACTOR: i64 = 32;
+    
+    /// Expected score (basis points) of the stronger player, for rating
+    /// gaps of 0, 50, 100 .. 800. Larger gaps use the last entry.
+    const EXPECTED_BPS: [i64; 17] = [
+        5000, 5715, 6401, 7034, 7597, 8083, 8490, 8823, 9091,
+        9302, 9468, 9595, 9693, 9768, 9825, 9868, 9901,
+    ];
+    const EXPECTED_STEP: i64 = 50;
+
+    /// Expected score (basis points) of a player `diff` points above their opponent
+    pub fn expected_bps(diff: i64) -> i64 {
+        let gap = diff.abs();
+        let index = (gap / Self::EXPECTED_STEP) as usize;
+        let expected = if index + 1 >= Self::EXPECTED_BPS.len() {
+            Self::EXPECTED_BPS[Self::EXPECTED_BPS.len() - 1]
         } else {
-            0
+            // Linear interpolation between table entries
+            let low = Self::EXPECTED_BPS[index];
+            let high = Self::EXPECTED_BPS[index + 1];
+            low + (high - low) * (gap % Self::EXPECTED_STEP) / Self::EXPECTED_STEP
+        };
+        if diff >= 0 { expected } else { 10_000 - expected }
+    }
+
+    /// Multi-player Elo: every player is scored against every other by
+    /// finishing position (higher `scores` finish ahead, equal scores tie).
+    /// Returns each player's rating change.
+    pub fn rating_changes(ratings: &[u32], scores: &[u64]) -> Vec<i64> {
+        let n = ratings.len() as i64;
+        if n < 2 {
+            return vec![0; ratings.len()];
+        }
+
+        ratings
+            .iter()
+            .zip(scores.iter())
+            .enumerate()
+            .map(|(i, (&rating, &score))| {
+                let total: i64 = ratings
+                    .iter()
+                    .zip(scores.iter())
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, (&other_rating, &other_score))| {
+                        let actual = match score.cmp(&other_score) {
+                            std::cmp::Ordering::Greater => 10_000,
+                            std::cmp::Ordering::Equal => 5_000,
+                            std::cmp::Ordering::Less => 0,
+                        };
+                        actual - Self::expected_bps(rating as i64 - other_rating as i64)
+                    })
+                    .sum();
+                Self::K_FACTOR * total / (10_000 * (n - 1))
+            })
+            .collect()
+    }
+
+    /// Apply a rating change from a completed game
+    pub fn apply_change(&mut self, change: i64, now: i64) {
+        let rating = (self.rating as i64 + change).max(Self::MIN_RATING as i64);
+        self.rating = rating as u32;
+        self.peak_rating = self.peak_rating.max(self.rating);
+        self.games_rated = self.games_rated.saturating_add(1);
+        self.last_rated_at = now;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Settlement {
+    /// Game this settlement pays out of
+    pub poker_escrow: Pubkey,
+    
+    /// Hand being settled (the game's `total_hands_played` when proposed)
+    pub hand_index: u32,
+    
+    /// Proposed payouts (gross, before protocol fee)
+    #[max_len(10)]
+    pub payouts: Vec<Payout>,
+    
+    /// Hand result recorded once paid
+    pub hand_rank: u8,
+    #[max_len(32)]
+    pub hand_description: String,
+    
+    pub status: SettlementStatus,
+    pub proposed_at: i64,
+    pub challenge_deadline: i64,
+    
+    /// Player who disputed and the bond they staked
+    pub disputed_by: Option<Pubkey>,
+    pub dispute_bond: u64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Settlement {
+    pub const SEED_PREFIX: &'static [u8] = b"settlement";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Domain separator for attested settlement messages
+    pub const ATTESTATION_DOMAIN: &'static [u8] = b"poker_escrow:settle";
+
+    /// Message players sign (via the Ed25519 precompile) to attest a payout list
+    pub fn attestation_message(
+        poker_escrow: &Pubkey,
+        hand_index: u32,
+        payouts: &[Payout],
+        hand_rank: u8,
+        hand_description: &str,
+    ) -> Vec<u8> {
+        let mut message = Vec::with_capacity(
+            Self::ATTESTATION_DOMAIN.len() + 32 + 4 + 1 + 1 + payouts.len() * 40 + 1 + hand_description.len()
+        );
+        message.extend_from_slice(Self::ATTESTATION_DOMAIN);
+        message.extend_from_slice(poker_escrow.as_ref());
+        message.extend_from_slice(&hand_index.to_le_bytes());
+        message.push(hand_rank);
+        message.push(payouts.len() as u8);
+        for payout in payouts {
+            message.extend_from_slice(payout.player.as_ref());
+            message.extend_from_slice(&payout.amount.to_le_bytes());
         }
+        message.push(hand_description.len() as u8);
+        message.extend_from_slice(hand_description.as_bytes());
+        message
+    }
+
+    /// Check if a settlement has gone unpaid for `idle_timeout` past its
+    /// challenge window, whether or not it was disputed
+    pub fn is_expired(&self, idle_timeout: i64, now: i64) -> bool {
+        now - self.challenge_deadline > idle_timeout
     }
-}
\ No newline at end of file
+
+    /// Validate a payout list against the game and return its total
+    pub fn validate_payouts(poker_escrow: &PokerEscrow, payouts: &[Payout]) -> Result<u64> {
+        require!(
+            !payouts.is_empty() && payouts.len() <= 10,
+            crate::errors::PokerError::InvalidWinnerList
+        );
+
+        let mut total: u64 = 0;
+        for (i, payout) in payouts.iter().enumerate() {
+            require!(
+                poker_escrow.has_player(&payout.player),
+                crate::errors::PokerError::PlayerNotInGame
+            );
+            require!(
+                !payouts[..i].iter().any(|p| p.player == payout.player),
+                crate::errors::PokerError::InvalidWinnerList
+            );
+            total = total
+                .checked_add(payout.amount)
+                .ok_or(crate::errors::PokerError::MathOverflow)?;
+        }
+        require!(total <= poker_escrow.total_pot, crate::errors::PokerError::PayoutMismatch);
+
+        // Single hands close after their one payout, so it must take the
+        // whole pot or the rest would go to the server on close
+        if poker_escrow.game_type == GameType::SingleHand {
+            require!(total == poker_escrow.total_pot, crate::errors::PokerError::PayoutMismatch);
+        }
+
+        Ok(total)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Channel {
+    /// Cash game whose off-chain state is being settled
+    pub poker_escrow: Pubkey,
+    
+    /// Highest-nonce fully signed state submitted so far
+    pub nonce: u64,
+    #[max_len(10)]
+    pub stacks: Vec<StackUpdate>,
+    
+    /// Challenge window for newer states
+    pub submitted_at: i64,
+    pub challenge_deadline: i64,
+    
+    /// Paid rent for this account, refunded on finalize
+    pub rent_payer: Pubkey,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Channel {
+    pub const SEED_PREFIX: &'static [u8] = b"channel";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Domain separator for signed channel states
+    pub const STATE_DOMAIN: &'static [u8] = b"poker_escrow:channel";
+
+    /// Message players and server sign off-chain for each balance state
+    pub fn state_message(poker_escrow: &Pubkey, nonce: u64, stacks: &[StackUpdate]) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::STATE_DOMAIN.len() + 32 + 8 + 1 + stacks.len() * 40);
+        message.extend_from_slice(Self::STATE_DOMAIN);
+        message.extend_from_slice(poker_escrow.as_ref());
+        message.extend_from_slice(&nonce.to_le_bytes());
+        message.push(stacks.len() as u8);
+        for update in stacks {
+            message.extend_from_slice(update.player.as_ref());
+            message.extend_from_slice(&update.stack.to_le_bytes());
+        }
+        message
+    }
+
+    /// A state must give every seated player a stack and account for the whole pot
+    pub fn validate_stacks(poker_escrow: &PokerEscrow, stacks: &[StackUpdate]) -> Result<()> {
+        require!(
+            stacks.len() == poker_escrow.players.len(),
+            crate::errors::PokerError::InvalidChannelState
+        );
+
+        let mut total: u64 = 0;
+        for (i, update) in stacks.iter().enumerate() {
+            require!(
+                poker_escrow.has_player(&update.player),
+                crate::errors::PokerError::PlayerNotInGame
+            );
+            require!(
+                !stacks[..i].iter().any(|s| s.player == update.player),
+                crate::errors::PokerError::InvalidChannelState
+            );
+            total = total
+                .checked_add(update.stack)
+                .ok_or(crate::errors::PokerError::MathOverflow)?;
+        }
+        require!(total == poker_escrow.total_pot, crate::errors::PokerError::PayoutMismatch);
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub season_id: u32,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    
+    /// How payouts during the season convert to points
+    pub points: PointsFormula,
+    
+    /// Share of the protocol fee paid into the prize pool (basis points)
+    pub rake_share_bps: u16,
+    
+    /// Prize pool split by finishing place (basis points, sums to 10000)
+    #[max_len(10)]
+    pub prize_bps: Vec<u16>,
+    
+    /// Prize pool held in this account (in lamports, on top of rent)
+    pub prize_pool: u64,
+    
+    pub entries: u32,
+    
+    /// Leaderboard, one place per prize, highest points first
+    #[max_len(10)]
+    pub standings: Vec<SeasonStanding>,
+    
+    pub closed: bool,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Season {
+    pub const SEED_PREFIX: &'static [u8] = b"season";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    pub const MAX_PRIZES: usize = 10;
+
+    /// Validate schedule, rake and prize settings
+    pub fn validate(&self) -> Result<()> {
+        require!(self.ends_at > self.starts_at, crate::errors::PokerError::InvalidSeason);
+        require!(
+            self.rake_share_bps as u64 <= ProgramConfig::BPS_DENOMINATOR,
+            crate::errors::PokerError::InvalidSeason
+        );
+        require!(
+            !self.prize_bps.is_empty() && self.prize_bps.len() <= Self::MAX_PRIZES,
+            crate::errors::PokerError::InvalidSeason
+        );
+        require!(
+            self.prize_bps.iter().map(|&bps| bps as u64).sum::<u64>() == ProgramConfig::BPS_DENOMINATOR,
+            crate::errors::PokerError::InvalidSeason
+        );
+        Ok(())
+    }
+
+    /// Check if games are scoring points for this season
+    pub fn is_live(&self, now: i64) -> bool {
+        !self.closed && now >= self.starts_at && now < self.ends_at
+    }
+
+    /// Points earned by a payout
+    pub fn points_for(&self, winner_amount: u64, ends_tournament: bool) -> Result<u64> {
+        let for_amount = (winner_amount as u128)
+            .checked_mul(self.points.per_sol_won as u128)
+            .ok_or(crate::errors::PokerError::MathOverflow)?
+            / anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL as u128;
+        let bonus = if ends_tournament { self.points.tournament_win } else { 0 };
+        u64::try_from(for_amount)
+            .ok()
+            .and_then(|points| points.checked_add(self.points.per_hand_won))
+            .and_then(|points| points.checked_add(bonus))
+            .ok_or(crate::errors::PokerError::MathOverflow.into())
+    }
+
+    /// Record a player's new points total on the leaderboard
+    pub fn update_standing(&mut self, player: Pubkey, points: u64) {
+        self.standings.retain(|s| s.player != player);
+        self.standings.push(SeasonStanding { player, points });
+        // Stable sort: on a tie whoever got there first keeps the higher place
+        self.standings.sort_by_key(|s| std::cmp::Reverse(s.points));
+        self.standings.truncate(self.prize_bps.len());
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonEntry {
+    pub season: Pubkey,
+    pub player: Pubkey,
+    pub points: u64,
+    pub hands_won: u32,
+    pub joined_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SeasonEntry {
+    pub const SEED_PREFIX: &'static [u8] = b"season_entry";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct LimitSettings {
+    pub daily_buy_in_cap: u64,      // 0 = no cap
+    pub weekly_buy_in_cap: u64,     // 0 = no cap
+    pub excluded_until: i64,        // Self-exclusion end (0 = not excluded)
+}
+
+impl LimitSettings {
+    fn cap_is_looser(current: u64, requested: u64) -> bool {
+        current != 0 && (requested == 0 || requested > current)
+    }
+
+    /// Keep the stricter of the two settings for every field
+    fn strictest(&self, other: &Self) -> Self {
+        Self {
+            daily_buy_in_cap: if Self::cap_is_looser(self.daily_buy_in_cap, other.daily_buy_in_cap) {
+                self.daily_buy_in_cap
+            } else {
+                other.daily_buy_in_cap
+            },
+            weekly_buy_in_cap: if Self::cap_is_looser(self.weekly_buy_in_cap, other.weekly_buy_in_cap) {
+                self.weekly_buy_in_cap
+            } else {
+                other.weekly_buy_in_cap
+            },
+            excluded_until: self.excluded_until.max(other.excluded_until),
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerLimits {
+    /// Wallet these limits apply to
+    pub player: Pubkey,
+    
+    /// Limits in force
+    pub settings: LimitSettings,
+    
+    /// Requested loosening, applied once the cooling-off period has passed
+    pub pending: Option<LimitSettings>,
+    pub pending_effective_at: i64,
+    
+    /// Buy-ins in the current day and week (in lamports)
+    pub day_started_at: i64,
+    pub day_buy_ins: u64,
+    pub week_started_at: i64,
+    pub week_buy_ins: u64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PlayerLimits {
+    pub const SEED_PREFIX: &'static [u8] = b"player_limits";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    pub const DAY: i64 = 24 * 60 * 60;
+    pub const WEEK: i64 = 7 * Self::DAY;
+    
+    /// Delay before raising a cap or ending self-exclusion early takes effect
+    pub const COOLING_OFF: i64 = 7 * Self::DAY;
+
+    /// Apply a pending loosening whose cooling-off period has passed
+    pub fn apply_pending(&mut self, now: i64) {
+        if let Some(pending) = self.pending {
+            if now >= self.pending_effective_at {
+                self.settings = pending;
+                self.pending = None;
+            }
+        }
+    }
+
+    /// Change limits. Stricter settings apply now, looser ones are queued
+    /// behind the cooling-off period. Returns whether anything was queued.
+    pub fn request(&mut self, requested: LimitSettings, now: i64) -> bool {
+        self.apply_pending(now);
+        self.settings = self.settings.strictest(&requested);
+
+        if self.settings == requested {
+            self.pending = None;
+            false
+        } else {
+            self.pending = Some(requested);
+            self.pending_effective_at = now + Self::COOLING_OFF;
+            true
+        }
+    }
+
+    /// Check a buy-in against self-exclusion and caps, and record it
+    pub fn record_buy_in(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.apply_pending(now);
+        require!(now >= self.settings.excluded_until, crate::errors::PokerError::SelfExcluded);
+
+        if now >= self.day_started_at + Self::DAY {
+            self.day_started_at = now;
+            self.day_buy_ins = 0;
+        }
+        if now >= self.week_started_at + Self::WEEK {
+            self.week_started_at = now;
+            self.week_buy_ins = 0;
+        }
+
+        let day_total = self.day_buy_ins
+            .checked_add(amount)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        let week_total = self.week_buy_ins
+            .checked_add(amount)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        require!(
+            self.settings.daily_buy_in_cap == 0 || day_total <= self.settings.daily_buy_in_cap,
+            crate::errors::PokerError::BuyInLimitExceeded
+        );
+        require!(
+            self.settings.weekly_buy_in_cap == 0 || week_total <= self.settings.weekly_buy_in_cap,
+            crate::errors::PokerError::BuyInLimitExceeded
+        );
+
+        self.day_buy_ins = day_total;
+        self.week_buy_ins = week_total;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ComplianceAttestation {
+    /// Key that verified the player (e.g. a KYC provider)
+    pub attester: Pubkey,
+    pub player: Pubkey,
+    
+    /// Checks passed, as ComplianceAttestation::* flags
+    pub flags: u8,
+    
+    pub issued_at: i64,
+    pub expires_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ComplianceAttestation {
+    pub const SEED_PREFIX: &'static [u8] = b"compliance";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Attestation flags
+    pub const KYC_VERIFIED: u8 = 1 << 0;
+    pub const AGE_VERIFIED: u8 = 1 << 1;
+    pub const ALL_FLAGS: u8 = Self::KYC_VERIFIED | Self::AGE_VERIFIED;
+
+    /// Check if this attestation covers `flags` for `player`, issued by `attester`
+    pub fn covers(&self, attester: &Pubkey, player: &Pubkey, flags: u8) -> bool {
+        self.attester == *attester && self.player == *player && self.flags & flags == flags
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bankroll {
+    /// Wallet that owns these funds
+    pub player: Pubkey,
+    
+    /// Lifetime deposits and withdrawals (in lamports). The balance itself
+    /// is this account's lamports above rent, so winnings can be credited
+    /// by a plain transfer.
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    
+    pub created_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Bankroll {
+    pub const SEED_PREFIX: &'static [u8] = b"bankroll";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Time the player has to cancel a withdrawal made with a leaked key
+    pub const WITHDRAWAL_DELAY: i64 = 24 * 60 * 60;
+
+    /// Lamports available to play or withdraw. Also used for the treasury.
+    pub fn available(info: &AccountInfo) -> Result<u64> {
+        let rent = Rent::get()?.minimum_balance(info.data_len());
+        Ok(info.lamports().saturating_sub(rent))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SessionToken {
+    /// Wallet that authorized the session
+    pub player: Pubkey,
+    
+    /// Ephemeral key allowed to act for the player
+    pub session_key: Pubkey,
+    
+    /// Allowed actions, as SessionToken::* flags
+    pub permissions: u8,
+    
+    /// Total buy-ins the session may spend (in lamports)
+    pub spend_limit: u64,
+    pub spent: u64,
+    
+    pub created_at: i64,
+    pub expires_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SessionToken {
+    pub const SEED_PREFIX: &'static [u8] = b"session";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    pub const MAX_DURATION: i64 = 7 * 24 * 60 * 60;
+    
+    /// Session permission flags
+    pub const JOIN: u8 = 1 << 0;        // Join tables from the bankroll
+    pub const LEAVE: u8 = 1 << 1;       // Take refunds when a table is refundable
+    pub const CASH_OUT: u8 = 1 << 2;    // Withdraw the bankroll to the player's wallet
+    pub const ALL: u8 = Self::JOIN | Self::LEAVE | Self::CASH_OUT;
+
+    /// Check the session lets `session_key` perform `permission` for
+    /// `player` now, and charge `spend` against its spend limit
+    pub fn authorize(
+        &mut self,
+        player: &Pubkey,
+        session_key: &Pubkey,
+        permission: u8,
+        spend: u64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            self.player == *player &&
+            self.session_key == *session_key &&
+            self.permissions & permission == permission,
+            crate::errors::PokerError::UnauthorizedSession
+        );
+        require!(now < self.expires_at, crate::errors::PokerError::SessionExpired);
+
+        let spent = self.spent
+            .checked_add(spend)
+            .ok_or(crate::errors::PokerError::MathOverflow)?;
+        require!(spent <= self.spend_limit, crate::errors::PokerError::SessionLimitExceeded);
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    /// Fees withdrawn so far (in lamports). The balance is this account's
+    /// lamports above rent.
+    pub total_withdrawn: u64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const SEED_PREFIX: &'static [u8] = b"treasury";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Time the guardian has to cancel a withdrawal by a compromised admin
+    pub const WITHDRAWAL_DELAY: i64 = 2 * 24 * 60 * 60;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalRequest {
+    /// Bankroll or treasury being withdrawn from
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    
+    /// Paid the rent for this request, gets it back when it is closed
+    pub requested_by: Pubkey,
+    
+    pub requested_at: i64,
+    pub unlocks_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl WithdrawalRequest {
+    pub const SEED_PREFIX: &'static [u8] = b"withdrawal";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+}
+
+/// What a backer puts into a player's buy-in and gets back
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct BackerTerms {
+    pub backer: Pubkey,
+    
+    /// Part of the buy-in this backer funds (in lamports)
+    pub amount: u64,
+    
+    /// Share of the player's profit paid to this backer (basis points)
+    pub share_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Backer {
+    pub terms: BackerTerms,
+    pub funded: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Backing {
+    pub poker_escrow: Pubkey,
+    
+    /// Player whose buy-in is backed
+    pub player: Pubkey,
+    
+    /// Backers funding the buy-in; their amounts sum to it
+    #[max_len(5)]
+    pub backers: Vec<Backer>,
+    
+    /// Set once the buy-in has gone into the game. Winnings and refunds are
+    /// paid to this account until it is settled.
+    pub joined: bool,
+    
+    pub created_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Backing {
+    pub const SEED_PREFIX: &'static [u8] = b"backing";
+    
+    pub const MAX_SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    pub const MAX_BACKERS: usize = 5;
+
+    /// Validate terms against the game's buy-in
+    pub fn validate(terms: &[BackerTerms], buy_in: u64) -> Result<()> {
+        require!(
+            !terms.is_empty() && terms.len() <= Self::MAX_BACKERS,
+            crate::errors::PokerError::InvalidBacking
+        );
+        let mut total_amount: u64 = 0;
+        let mut total_share: u64 = 0;
+        for (i, backer) in terms.iter().enumerate() {
+            require!(
+                backer.amount > 0 && !terms[..i].iter().any(|b| b.backer == backer.backer),
+                crate::errors::PokerError::InvalidBacking
+            );
+            total_amount = total_amount
+                .checked_add(backer.amount)
+                .ok_or(crate::errors::PokerError::MathOverflow)?;
+            total_share += backer.share_bps as u64;
+        }
+        require!(
+            total_amount == buy_in && total_share <= ProgramConfig::BPS_DENOMINATOR,
+            crate::errors::PokerError::InvalidBacking
+        );
+        Ok(())
+    }
+
+    pub fn is_funded(&self) -> bool {
+        self.backers.iter().all(|b| b.funded)
+    }
+
+    /// Split `total` received from the game between the backers. Funded
+    /// backers get their stake back first (proportionally, if the player
+    /// lost part of it), then their share of any profit. The player keeps
+    /// the rest.
+    pub fn backer_payouts(&self, total: u64) -> Vec<u64> {
+        let staked: u64 = self.backers
+            .iter()
+            .filter(|b| b.funded)
+            .map(|b| b.terms.amount)
+            .sum();
+        if staked == 0 {
+            return vec![0; self.backers.len()];
+        }
+        let returned = total.min(staked);
+        let profit = total - returned;
+
+        self.backers
+            .iter()
+            .map(|b| {
+                if !b.funded {
+                    return 0;
+                }
+                let stake = returned as u128 * b.terms.amount as u128 / staked as u128;
+                let share = profit as u128 * b.terms.share_bps as u128 /
+                    ProgramConfig::BPS_DENOMINATOR as u128;
+                (stake + share) as u64
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{escrow, deposit, table, settlement, backing, limits, player_limits};
+
+    #[test]
+    fn dealer_seed_changes_with_the_slot_hash() {
+        let escrow = table(6, &[(0, SeatStatus::Active), (3, SeatStatus::Active)]);
+        let seed = escrow.compute_dealer_seed(&[1; 32]);
+
+        // Anyone can recompute it, but each slot gives a different one
+        assert_eq!(escrow.compute_dealer_seed(&[1; 32]), seed);
+        assert_ne!(escrow.compute_dealer_seed(&[2; 32]), seed);
+    }
+
+    #[test]
+    fn dealer_from_seed_picks_an_active_seat() {
+        let escrow = table(9, &[
+            (1, SeatStatus::Active),
+            (4, SeatStatus::SittingOut),
+            (6, SeatStatus::Active),
+            (8, SeatStatus::Eliminated),
+        ]);
+
+        let dealers: Vec<u8> = (0..64u8)
+            .map(|slot| escrow.dealer_from_seed(&escrow.compute_dealer_seed(&[slot; 32])))
+            .collect();
+        assert!(dealers.iter().all(|&seat| escrow.is_seat_active(seat)));
+        assert!(dealers.contains(&1) && dealers.contains(&6));
+    }
+
+    #[test]
+    fn button_skips_empty_sitting_out_and_eliminated_seats() {
+        let mut escrow = table(8, &[
+            (0, SeatStatus::Active),
+            (2, SeatStatus::SittingOut),
+            (3, SeatStatus::Eliminated),
+            (5, SeatStatus::Active),
+        ]);
+
+        escrow.rotate_dealer().unwrap();
+        assert_eq!(escrow.dealer_position, 5);
+    }
+
+    #[test]
+    fn button_wraps_around_past_the_last_seat() {
+        let mut escrow = table(6, &[(1, SeatStatus::Active), (3, SeatStatus::Active), (5, SeatStatus::Active)]);
+        escrow.dealer_position = 5;
+
+        escrow.rotate_dealer().unwrap();
+        assert_eq!(escrow.dealer_position, 1);
+
+        // Wrapping round skips seats that sat out
+        let first = escrow.players[0].player;
+        escrow.set_seat_status(&first, SeatStatus::SittingOut).unwrap();
+        escrow.dealer_position = 3;
+        escrow.rotate_dealer().unwrap();
+        assert_eq!(escrow.dealer_position, 5);
+        escrow.rotate_dealer().unwrap();
+        assert_eq!(escrow.dealer_position, 3);
+    }
+
+    #[test]
+    fn button_stays_with_the_last_active_player() {
+        let mut escrow = table(4, &[(1, SeatStatus::Eliminated), (2, SeatStatus::Active)]);
+        escrow.dealer_position = 2;
+
+        escrow.rotate_dealer().unwrap();
+        assert_eq!(escrow.dealer_position, 2);
+    }
+
+    #[test]
+    fn active_games_refund_once_idle_past_the_timeout() {
+        let mut escrow = escrow();
+        escrow.status = GameStatus::Active;
+        escrow.idle_timeout = PokerEscrow::MIN_IDLE_TIMEOUT;
+        escrow.touch(1_000);
+
+        let deadline = 1_000 + PokerEscrow::MIN_IDLE_TIMEOUT;
+        assert!(!escrow.is_refundable(deadline));
+        assert!(escrow.is_refundable(deadline + 1));
+
+        // Any server action restarts the clock
+        escrow.touch(deadline);
+        assert!(!escrow.is_refundable(deadline + 1));
+
+        // An open settlement holds the pot until it is paid or expires
+        escrow.status = GameStatus::Settling;
+        assert!(!escrow.is_refundable(i64::MAX));
+    }
+
+    #[test]
+    fn settlements_expire_the_idle_timeout_after_their_window() {
+        let settlement = settlement(1_000);
+        let deadline = 1_000 + PokerEscrow::MIN_IDLE_TIMEOUT;
+
+        assert!(!settlement.is_expired(PokerEscrow::MIN_IDLE_TIMEOUT, deadline));
+        assert!(settlement.is_expired(PokerEscrow::MIN_IDLE_TIMEOUT, deadline + 1));
+    }
+
+    #[test]
+    fn pending_games_refund_after_the_start_timeout() {
+        let mut escrow = escrow();
+        assert!(!escrow.is_refundable(PokerEscrow::REFUND_TIMEOUT));
+        assert!(escrow.is_refundable(PokerEscrow::REFUND_TIMEOUT + 1));
+
+        escrow.status = GameStatus::Abandoned;
+        assert!(escrow.is_refundable(0));
+        escrow.status = GameStatus::Completed;
+        assert!(!escrow.is_refundable(i64::MAX));
+    }
+
+    #[test]
+    fn refunds_follow_the_last_reported_stacks() {
+        let mut escrow = escrow();
+        escrow.players = vec![deposit(100, 150), deposit(100, 40), deposit(100, 10)];
+        escrow.total_pot = 299;
+        let [a, b, c] = [0, 1, 2].map(|i| escrow.players[i].player);
+
+        // Proportional to stacks, rounded down
+        assert_eq!(escrow.refund_share(&a).unwrap(), 224);
+        assert_eq!(escrow.refund_share(&b).unwrap(), 59);
+        assert_eq!(escrow.refund_share(&c).unwrap(), 14);
+
+        // The last player out takes the rounding dust
+        escrow.players.truncate(1);
+        assert_eq!(escrow.refund_share(&a).unwrap(), 299);
+    }
+
+    #[test]
+    fn backer_payouts_return_stakes_before_profit() {
+        // 1/3 and 2/3 of the buy-in, a third of the profit each
+        let backing = backing(&[(1, 3333, true), (2, 3333, true)]);
+
+        // Stakes back in full, then 7 * 33.33% = 2.33 rounded down each
+        assert_eq!(backing.backer_payouts(10), vec![3, 4]);
+        // Stakes only, nothing left to share
+        assert_eq!(backing.backer_payouts(3), vec![1, 2]);
+    }
+
+    #[test]
+    fn backer_payouts_round_down_in_the_players_favour() {
+        let backing = backing(&[(1, 5000, true), (2, 5000, true)]);
+
+        // A loss returns stakes proportionally, 2/3 and 4/3 rounded down
+        let payouts = backing.backer_payouts(2);
+        assert_eq!(payouts, vec![0, 1]);
+
+        // Never more than was received, whatever the rounding
+        for total in 0..50 {
+            assert!(backing.backer_payouts(total).iter().sum::<u64>() <= total);
+        }
+    }
+
+    #[test]
+    fn backer_payouts_skip_unfunded_backers() {
+        // Called off before the join: the funded backer gets their stake back
+        assert_eq!(backing(&[(6, 3000, true), (4, 2000, false)]).backer_payouts(6), vec![6, 0]);
+        assert_eq!(backing(&[(6, 3000, false)]).backer_payouts(6), vec![0]);
+    }
+
+    #[test]
+    fn strictest_keeps_the_lower_cap_and_the_later_exclusion() {
+        let a = limits(5, 0, 100);
+        let b = limits(3, 20, 50);
+
+        // A cap of 0 means no cap, so any cap is stricter than it
+        assert_eq!(a.strictest(&b), limits(3, 20, 100));
+        assert_eq!(b.strictest(&a), limits(3, 20, 100));
+        assert_eq!(limits(0, 0, 0).strictest(&limits(0, 0, 0)), limits(0, 0, 0));
+    }
+
+    #[test]
+    fn stricter_limits_apply_immediately() {
+        let mut player_limits = player_limits(limits(10, 50, 0));
+
+        assert!(!player_limits.request(limits(5, 50, 1_000), 100));
+        assert_eq!(player_limits.settings, limits(5, 50, 1_000));
+        assert_eq!(player_limits.pending, None);
+    }
+
+    #[test]
+    fn looser_limits_wait_out_the_cooling_off_period() {
+        let mut player_limits = player_limits(limits(10, 50, 0));
+
+        // Lower daily cap now, lifting the weekly cap has to wait
+        assert!(player_limits.request(limits(5, 0, 0), 100));
+        assert_eq!(player_limits.settings, limits(5, 50, 0));
+        assert_eq!(player_limits.pending, Some(limits(5, 0, 0)));
+        assert_eq!(player_limits.pending_effective_at, 100 + PlayerLimits::COOLING_OFF);
+
+        player_limits.apply_pending(99 + PlayerLimits::COOLING_OFF);
+        assert_eq!(player_limits.settings, limits(5, 50, 0));
+
+        player_limits.apply_pending(100 + PlayerLimits::COOLING_OFF);
+        assert_eq!(player_limits.settings, limits(5, 0, 0));
+        assert_eq!(player_limits.pending, None);
+    }
+
+    #[test]
+    fn stricter_request_cancels_a_pending_loosening() {
+        let mut player_limits = player_limits(limits(10, 0, 0));
+
+        assert!(player_limits.request(limits(20, 0, 0), 100));
+        assert!(!player_limits.request(limits(8, 0, 0), 200));
+        assert_eq!(player_limits.pending, None);
+
+        player_limits.apply_pending(100 + PlayerLimits::COOLING_OFF);
+        assert_eq!(player_limits.settings, limits(8, 0, 0));
+    }
+}
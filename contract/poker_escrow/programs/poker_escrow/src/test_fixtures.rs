@@ -0,0 +1,140 @@
+// programs/poker_escrow/src/test_fixtures.rs
+//! Accounts shared by the unit tests, built in memory with every field at a
+//! known default so each test only sets what it exercises
+use anchor_lang::prelude::*;
+use crate::utils::TOKEN_METADATA_PROGRAM_ID;
+use crate::state::{PokerEscrow, GameStatus, PlayerDeposit, SeatStatus, Settlement, SettlementStatus, Backing, Backer, BackerTerms, LimitSettings, PlayerLimits};
+
+/// Escrow read from an all-zero account: a pending SingleHand game with
+/// no players and every timestamp at 0
+pub fn escrow() -> PokerEscrow {
+    PokerEscrow::deserialize(&mut &vec![0u8; PokerEscrow::MAX_SIZE][..]).unwrap()
+}
+
+/// Active player seated at seat 0 with `amount` bought in and `stack` left
+pub fn deposit(amount: u64, stack: u64) -> PlayerDeposit {
+    let player = Pubkey::new_unique();
+    PlayerDeposit {
+        player,
+        amount,
+        timestamp: 0,
+        seat: 0,
+        status: SeatStatus::Active,
+        stack,
+        payout_to: player,
+        bounty: 0,
+        winnings: 0,
+        eliminated: 0,
+    }
+}
+
+/// Started escrow with `max_players` seats and a player in each of `seats`
+pub fn table(max_players: u8, seats: &[(u8, SeatStatus)]) -> PokerEscrow {
+    let mut escrow = escrow();
+    escrow.status = GameStatus::Active;
+    escrow.max_players = max_players;
+    escrow.players = seats
+        .iter()
+        .map(|&(seat, status)| PlayerDeposit { seat, status, ..deposit(1, 1) })
+        .collect();
+    escrow.current_players = seats.len() as u8;
+    escrow
+}
+
+/// Undisputed settlement of the first hand whose window closes at `challenge_deadline`
+pub fn settlement(challenge_deadline: i64) -> Settlement {
+    Settlement {
+        poker_escrow: Pubkey::new_unique(),
+        hand_index: 0,
+        payouts: Vec::new(),
+        hand_rank: 0,
+        hand_description: String::new(),
+        status: SettlementStatus::Proposed,
+        proposed_at: 0,
+        challenge_deadline,
+        disputed_by: None,
+        dispute_bond: 0,
+        bump: 0,
+    }
+}
+
+/// Backing of a joined player by (amount, share_bps, funded) backers
+pub fn backing(backers: &[(u64, u16, bool)]) -> Backing {
+    Backing {
+        poker_escrow: Pubkey::new_unique(),
+        player: Pubkey::new_unique(),
+        backers: backers
+            .iter()
+            .map(|&(amount, share_bps, funded)| Backer {
+                terms: BackerTerms { backer: Pubkey::new_unique(), amount, share_bps },
+                funded,
+            })
+            .collect(),
+        joined: true,
+        created_at: 0,
+        bump: 0,
+    }
+}
+
+pub fn limits(daily: u64, weekly: u64, excluded_until: i64) -> LimitSettings {
+    LimitSettings { daily_buy_in_cap: daily, weekly_buy_in_cap: weekly, excluded_until }
+}
+
+/// Limits in force with nothing pending and no buy-ins recorded
+pub fn player_limits(settings: LimitSettings) -> PlayerLimits {
+    PlayerLimits {
+        player: Pubkey::new_unique(),
+        settings,
+        pending: None,
+        pending_effective_at: 0,
+        day_started_at: 0,
+        day_buy_ins: 0,
+        week_started_at: 0,
+        week_buy_ins: 0,
+        bump: 0,
+    }
+}
+
+/// Metaplex metadata as laid out on chain, with optional creators,
+/// edition nonce and (verified, key) collection
+pub fn metadata(mint: &Pubkey, creators: usize, edition_nonce: Option<u8>, collection: Option<(bool, Pubkey)>) -> Vec<u8> {
+    let mut data = vec![4]; // Key::MetadataV1
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(mint.as_ref());
+    for field in ["Poker Pass", "PASS", "https://example.com/pass.json"] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+    data.extend_from_slice(&500u16.to_le_bytes());
+    if creators > 0 {
+        data.push(1);
+        data.extend_from_slice(&(creators as u32).to_le_bytes());
+        for _ in 0..creators {
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+            data.extend_from_slice(&[1, 100 / creators as u8]);
+        }
+    } else {
+        data.push(0);
+    }
+    data.extend_from_slice(&[1, 1]); // primary_sale_happened, is_mutable
+    match edition_nonce {
+        Some(nonce) => data.extend_from_slice(&[1, nonce]),
+        None => data.push(0),
+    }
+    data.extend_from_slice(&[1, 0]); // token_standard: NonFungible
+    match collection {
+        Some((verified, key)) => {
+            data.extend_from_slice(&[1, verified as u8]);
+            data.extend_from_slice(key.as_ref());
+        }
+        None => data.push(0),
+    }
+    data
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    ).0
+}
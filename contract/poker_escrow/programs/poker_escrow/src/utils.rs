@@ -0,0 +1,539 @@
+// programs/poker_escrow/src/utils.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::state::{PokerEscrow, GameStatus, GameType, GameServerProfile, ProgramConfig, Payout, PlayerProfile, PlayerRating, Season, SeasonEntry, SessionToken};
+use crate::errors::PokerError;
+
+/// Move lamports out of a program-owned account
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(PokerError::InsufficientBalance)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(PokerError::MathOverflow)?;
+    Ok(())
+}
+
+/// Require `player` to have signed, or `authority` to be a session key the
+/// player authorized for `permission`. Session spends count against the
+/// session's limit.
+pub fn authorize_player(
+    player: &AccountInfo,
+    authority: &Pubkey,
+    session_token: Option<&mut Account<SessionToken>>,
+    permission: u8,
+    spend: u64,
+) -> Result<()> {
+    if player.is_signer {
+        return Ok(());
+    }
+    let session_token = session_token.ok_or(PokerError::UnauthorizedSession)?;
+    session_token.authorize(player.key, authority, permission, spend, Clock::get()?.unix_timestamp)
+}
+
+/// Refund every player passed in `accounts`, as (payout account, player
+/// profile) pairs, their share of the pot, removing them from the game.
+/// The payout account is the player's wallet or bankroll, as they joined.
+/// Returns the total amount refunded.
+pub fn refund_players<'info>(
+    poker_escrow: &mut Account<'info, PokerEscrow>,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<u64> {
+    require!(accounts.len().is_multiple_of(2), PokerError::InvalidRefundAccount);
+    let mut total_refunded: u64 = 0;
+
+    for pair in accounts.chunks(2) {
+        let (payout_account, profile_info) = (&pair[0], &pair[1]);
+        require!(payout_account.is_writable, PokerError::InvalidRefundAccount);
+
+        // Profiles only exist at their player's PDA, so the stored player
+        // identifies whose deposit this is
+        let mut profile: Account<PlayerProfile> = Account::try_from(profile_info)?;
+        let player = profile.player;
+        require!(poker_escrow.has_player(&player), PokerError::InvalidPlayerProfile);
+        require_keys_eq!(
+            poker_escrow.payout_address(&player),
+            *payout_account.key,
+            PokerError::InvalidRefundAccount
+        );
+
+        let refund_amount = poker_escrow.remove_player(&player)?;
+        transfer_lamports(&poker_escrow.to_account_info(), payout_account, refund_amount)?;
+        profile.record_refund(refund_amount)?;
+        profile.exit(&crate::ID)?;
+        total_refunded = total_refunded
+            .checked_add(refund_amount)
+            .ok_or(PokerError::MathOverflow)?;
+
+        msg!("Refunded {} lamports to player {}", refund_amount, player);
+    }
+
+    Ok(total_refunded)
+}
+
+/// Pay a winner out of the pot to their payout account (wallet or bankroll),
+/// sending the protocol fee to the treasury less the rake share of a live
+/// `season`, which goes to its prize pool.
+/// Returns the amount the winner received and the fee taken.
+pub fn pay_winner<'info>(
+    poker_escrow: &mut Account<'info, PokerEscrow>,
+    winner: &Pubkey,
+    payout_account: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    season: Option<&mut Account<'info, Season>>,
+    amount: u64,
+) -> Result<(u64, u64)> {
+    require!(poker_escrow.has_player(winner), PokerError::PlayerNotInGame);
+    require_keys_eq!(
+        poker_escrow.payout_address(winner),
+        *payout_account.key,
+        PokerError::PayoutAccountMismatch
+    );
+    require!(amount <= poker_escrow.prize_pool(), PokerError::PayoutMismatch);
+
+    // Protocol fee goes to the treasury, the rest to the winner
+    let fee = ProgramConfig::fee_for(amount, poker_escrow.fee_bps)?;
+    let winner_amount = amount - fee;
+
+    let escrow_info = poker_escrow.to_account_info();
+    transfer_lamports(&escrow_info, payout_account, winner_amount)?;
+    collect_fee(&escrow_info, treasury, season, fee)?;
+
+    poker_escrow.total_pot -= amount;
+    if let Some(deposit) = poker_escrow.players.iter_mut().find(|p| p.player == *winner) {
+        deposit.winnings = deposit.winnings
+            .checked_add(amount)
+            .ok_or(PokerError::MathOverflow)?;
+    }
+    poker_escrow.paid_out = poker_escrow.paid_out
+        .checked_add(amount)
+        .ok_or(PokerError::MathOverflow)?;
+
+    Ok((winner_amount, fee))
+}
+
+/// Send a protocol fee out of the pot to the treasury, less the rake share of
+/// a live `season`, which goes to its prize pool
+pub fn collect_fee<'info>(
+    escrow_info: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    season: Option<&mut Account<'info, Season>>,
+    fee: u64,
+) -> Result<()> {
+    let mut season_cut = 0;
+    if let Some(season) = season {
+        season_cut = ProgramConfig::fee_for(fee, season.rake_share_bps)?;
+        if season_cut > 0 {
+            transfer_lamports(escrow_info, &season.to_account_info(), season_cut)?;
+            season.prize_pool = season.prize_pool
+                .checked_add(season_cut)
+                .ok_or(PokerError::MathOverflow)?;
+        }
+    }
+    if fee > season_cut {
+        transfer_lamports(escrow_info, treasury, fee - season_cut)?;
+    }
+    Ok(())
+}
+
+/// The game's season if it is live. Games with a season must pass it, so it
+/// can't be left out to skip its rake share and points.
+pub fn live_season<'a, 'info>(
+    poker_escrow: &PokerEscrow,
+    season: Option<&'a mut Account<'info, Season>>,
+    now: i64,
+) -> Result<Option<&'a mut Account<'info, Season>>> {
+    require!(
+        poker_escrow.season == season.as_ref().map(|s| s.key()),
+        PokerError::SeasonMismatch
+    );
+    Ok(season.filter(|s| s.is_live(now)))
+}
+
+/// Credit season points for a payout to the winner's season entry
+pub fn credit_season_points(
+    season: &mut Account<Season>,
+    entry: &mut SeasonEntry,
+    winner: &Pubkey,
+    winner_amount: u64,
+    ends_tournament: bool,
+) -> Result<()> {
+    require_keys_eq!(entry.season, season.key(), PokerError::SeasonMismatch);
+    require_keys_eq!(entry.player, *winner, PokerError::SeasonMismatch);
+
+    let points = season.points_for(winner_amount, ends_tournament)?;
+    entry.points = entry.points
+        .checked_add(points)
+        .ok_or(PokerError::MathOverflow)?;
+    entry.hands_won = entry.hands_won
+        .checked_add(1)
+        .ok_or(PokerError::MathOverflow)?;
+    season.update_standing(*winner, entry.points);
+
+    msg!("Season {}: {} points to {} ({} total)", season.season_id, points, winner, entry.points);
+    Ok(())
+}
+
+/// Read the optional season entry passed for a player, the program id
+/// standing in for players who didn't sign up
+pub fn season_entry<'info>(info: &'info AccountInfo<'info>) -> Result<Option<Account<'info, SeasonEntry>>> {
+    if *info.key == crate::ID {
+        return Ok(None);
+    }
+    Ok(Some(Account::try_from(info)?))
+}
+
+/// Update the rating of every player in a completed game, ranking them by
+/// where they placed. `accounts` must hold exactly one rating account per
+/// player, in the order they joined.
+pub fn rate_players<'info>(
+    poker_escrow: &PokerEscrow,
+    accounts: &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<()> {
+    require!(
+        accounts.len() == poker_escrow.players.len(),
+        PokerError::RatingAccountsMismatch
+    );
+    let mut ratings: Vec<Account<PlayerRating>> = Vec::with_capacity(accounts.len());
+    for (info, deposit) in accounts.iter().zip(poker_escrow.players.iter()) {
+        let rating: Account<PlayerRating> = Account::try_from(info)?;
+        require_keys_eq!(rating.player, deposit.player, PokerError::RatingAccountsMismatch);
+        ratings.push(rating);
+    }
+
+    let current: Vec<u32> = ratings.iter().map(|r| r.rating).collect();
+    let scores: Vec<u64> = poker_escrow.players
+        .iter()
+        .map(|deposit| poker_escrow.placement_score(deposit))
+        .collect();
+    let changes = PlayerRating::rating_changes(&current, &scores);
+
+    for (rating, change) in ratings.iter_mut().zip(changes) {
+        rating.apply_change(change, now);
+        rating.exit(&crate::ID)?;
+        msg!("Rating of {} changed by {} to {}", rating.player, change, rating.rating);
+    }
+
+    Ok(())
+}
+
+/// Complete the current hand after its payouts. Single-hand games are closed
+/// with rent returned to the game server owner; multi-hand games rotate the
+/// dealer and complete once the pot is empty. Completed single-hand and
+/// tournament games update the ratings passed in `rating_accounts`.
+pub fn finish_hand<'info>(
+    poker_escrow: &mut Account<'info, PokerEscrow>,
+    server_authority: &AccountInfo<'info>,
+    rating_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let game_id = poker_escrow.game_id;
+    poker_escrow.complete_hand()?;
+
+    if poker_escrow.completes_rated_game(0) {
+        rate_players(poker_escrow, rating_accounts, Clock::get()?.unix_timestamp)?;
+    }
+
+    match poker_escrow.game_type {
+        GameType::SingleHand => {
+            // Single hand games close after one payout, which must take the
+            // whole pot or the rest would go to the server on close
+            require!(poker_escrow.total_pot == 0, PokerError::PayoutMismatch);
+            poker_escrow.close(server_authority.clone())?;
+            msg!("Single-hand poker game {} completed and closed", game_id);
+        },
+        GameType::Tournament | GameType::KnockoutTournament | GameType::CashGame => {
+            // Multi-hand games rotate the dealer after each payout
+            poker_escrow.rotate_dealer()?;
+
+            // If pot is empty, mark game as completed
+            if poker_escrow.total_pot == 0 {
+                poker_escrow.status = GameStatus::Completed;
+                msg!("Multi-hand poker game {} completed - all funds distributed", game_id);
+            } else {
+                msg!("Hand completed in poker game {}, ready for next hand", game_id);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Accounts passed per winner to `pay_winners`
+pub const WINNER_ACCOUNTS: usize = 3;
+
+/// Pay a list of winners and record a hand result for each, crediting a live
+/// `season`. `winners` holds (payout account, player profile, season entry)
+/// triples in the same order as `payouts`, with the program id as the entry
+/// of winners not in the season.
+pub fn pay_winners<'info>(
+    poker_escrow: &mut Account<'info, PokerEscrow>,
+    payouts: &[Payout],
+    winners: &'info [AccountInfo<'info>],
+    treasury: &AccountInfo<'info>,
+    mut season: Option<&mut Account<'info, Season>>,
+    hand_rank: u8,
+    hand_description: &str,
+) -> Result<()> {
+    require!(
+        payouts.len() * WINNER_ACCOUNTS == winners.len(),
+        PokerError::PayoutAccountMismatch
+    );
+
+    for (payout, accounts) in payouts.iter().zip(winners.chunks(WINNER_ACCOUNTS)) {
+        let (payout_account, profile_info, entry_info) = (&accounts[0], &accounts[1], &accounts[2]);
+        require!(payout_account.is_writable, PokerError::PayoutAccountMismatch);
+
+        // Profiles only exist at their player's PDA
+        let mut profile: Account<PlayerProfile> = Account::try_from(profile_info)?;
+        require_keys_eq!(profile.player, payout.player, PokerError::InvalidPlayerProfile);
+
+        let (winner_amount, fee) = pay_winner(
+            poker_escrow,
+            &payout.player,
+            payout_account,
+            treasury,
+            season.as_deref_mut(),
+            payout.amount,
+        )?;
+        poker_escrow.add_hand_result(payout.player, hand_rank, hand_description.to_string(), winner_amount)?;
+        profile.record_win(payout.amount, winner_amount)?;
+        profile.exit(&crate::ID)?;
+
+        if let (Some(season), Some(mut entry)) = (season.as_mut(), season_entry(entry_info)?) {
+            let ends_tournament = poker_escrow.game_type.is_tournament() && poker_escrow.total_pot == 0;
+            credit_season_points(season, &mut entry, &payout.player, winner_amount, ends_tournament)?;
+            entry.exit(&crate::ID)?;
+        }
+
+        msg!("Paid {} lamports to winner {} (fee: {})", winner_amount, payout.player, fee);
+    }
+
+    Ok(())
+}
+
+/// Size of one signature offsets entry in Ed25519 precompile instruction data
+const ED25519_OFFSETS_SIZE: usize = 14;
+/// Offsets header: signature count (u8) plus padding (u8)
+const ED25519_HEADER_SIZE: usize = 2;
+
+fn read_u16(data: &[u8], offset: usize) -> Result<usize> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(PokerError::InvalidEd25519Instruction)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+}
+
+/// Collect the public keys that signed `expected_message` in Ed25519 precompile
+/// instructions placed before the current instruction. The precompile has
+/// already verified these signatures, or the transaction would have failed.
+pub fn ed25519_signers(instructions_sysvar: &AccountInfo, expected_message: &[u8]) -> Result<Vec<Pubkey>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let mut signers = Vec::new();
+
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+        if instruction.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        let data = &instruction.data;
+        let count = *data.first().ok_or(PokerError::InvalidEd25519Instruction)? as usize;
+
+        for i in 0..count {
+            let start = ED25519_HEADER_SIZE + i * ED25519_OFFSETS_SIZE;
+            let signature_ix = read_u16(data, start + 2)?;
+            let public_key_offset = read_u16(data, start + 4)?;
+            let public_key_ix = read_u16(data, start + 6)?;
+            let message_offset = read_u16(data, start + 8)?;
+            let message_size = read_u16(data, start + 10)?;
+            let message_ix = read_u16(data, start + 12)?;
+
+            // Only trust data embedded in the precompile instruction itself
+            let this_instruction = u16::MAX as usize;
+            require!(
+                signature_ix == this_instruction &&
+                public_key_ix == this_instruction &&
+                message_ix == this_instruction,
+                PokerError::InvalidEd25519Instruction
+            );
+
+            let message = data
+                .get(message_offset..message_offset + message_size)
+                .ok_or(PokerError::InvalidEd25519Instruction)?;
+            if message != expected_message {
+                continue;
+            }
+
+            let public_key = data
+                .get(public_key_offset..public_key_offset + 32)
+                .ok_or(PokerError::InvalidEd25519Instruction)?;
+            let signer = Pubkey::try_from(public_key)
+                .map_err(|_| PokerError::InvalidEd25519Instruction)?;
+            if !signers.contains(&signer) {
+                signers.push(signer);
+            }
+        }
+    }
+
+    Ok(signers)
+}
+
+/// Require every seated player and the game server to have signed `message`.
+/// The server's owner or operator may sign either in an Ed25519 instruction
+/// or by submitting the transaction itself.
+pub fn require_fully_signed(
+    poker_escrow: &PokerEscrow,
+    instructions_sysvar: &AccountInfo,
+    game_server: &GameServerProfile,
+    submitter: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let signers = ed25519_signers(instructions_sysvar, message)?;
+
+    let players_signed = poker_escrow.players
+        .iter()
+        .all(|p| signers.contains(&p.player));
+    let server_signed = game_server.can_operate(submitter) ||
+        signers.iter().any(|signer| game_server.can_operate(signer));
+
+    require!(players_signed && server_signed, PokerError::ChannelStateNotFullySigned);
+    Ok(())
+}
+
+/// Require the escrow's M-of-N payout signers when moving `amount` lamports
+/// out, on top of what the game already paid. The submitting operator and any
+/// signer in `cosigners` count.
+pub fn require_payout_cosigners(
+    poker_escrow: &PokerEscrow,
+    operator: &Pubkey,
+    cosigners: &[AccountInfo],
+    amount: u64,
+) -> Result<()> {
+    if !poker_escrow.requires_cosigners(amount) {
+        return Ok(());
+    }
+
+    let mut signers: Vec<Pubkey> = cosigners
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| *account.key)
+        .collect();
+    signers.push(*operator);
+
+    require!(
+        poker_escrow.count_payout_signers(&signers) >= poker_escrow.payout_threshold as usize,
+        PokerError::InsufficientPayoutSigners
+    );
+    Ok(())
+}
+
+/// Metaplex Token Metadata program, owner of NFT metadata accounts
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Read the verified collection of `mint` from its Metaplex metadata account.
+/// Parsed by hand to avoid depending on the Metaplex crates; the layout is
+/// key, update authority, mint, name, symbol, uri, seller fee, creators,
+/// primary sale, is mutable, edition nonce, token standard, collection.
+pub fn verified_collection(metadata: &AccountInfo, mint: &Pubkey) -> Result<Option<Pubkey>> {
+    require_keys_eq!(*metadata.owner, TOKEN_METADATA_PROGRAM_ID, PokerError::InvalidTokenMetadata);
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    require_keys_eq!(*metadata.key, expected, PokerError::InvalidTokenMetadata);
+
+    let data = metadata.try_borrow_data()?;
+    let mut offset = 1 + 32 + 32; // key, update_authority, mint
+    let take = |offset: &mut usize, len: usize| -> Result<&[u8]> {
+        let bytes = data
+            .get(*offset..*offset + len)
+            .ok_or(PokerError::InvalidTokenMetadata)?;
+        *offset += len;
+        Ok(bytes)
+    };
+    let read_u32 = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+
+    // name, symbol, uri
+    for _ in 0..3 {
+        let len = read_u32(take(&mut offset, 4)?);
+        take(&mut offset, len)?;
+    }
+    take(&mut offset, 2)?; // seller_fee_basis_points
+    if take(&mut offset, 1)?[0] == 1 {
+        // creators: address, verified, share
+        let count = read_u32(take(&mut offset, 4)?);
+        take(&mut offset, count * 34)?;
+    }
+    take(&mut offset, 2)?; // primary_sale_happened, is_mutable
+    for _ in 0..2 {
+        // edition_nonce, token_standard
+        if take(&mut offset, 1)?[0] == 1 {
+            take(&mut offset, 1)?;
+        }
+    }
+    if take(&mut offset, 1)?[0] == 0 {
+        return Ok(None);
+    }
+    let verified = take(&mut offset, 1)?[0] == 1;
+    let key = Pubkey::try_from(take(&mut offset, 32)?)
+        .map_err(|_| PokerError::InvalidTokenMetadata)?;
+
+    Ok(verified.then_some(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{metadata, metadata_address};
+
+    fn collection_of(address: Pubkey, owner: Pubkey, mint: &Pubkey, mut data: Vec<u8>) -> Result<Option<Pubkey>> {
+        let mut lamports = 0;
+        let info = AccountInfo::new(&address, false, false, &mut lamports, &mut data, &owner, false, 0);
+        verified_collection(&info, mint)
+    }
+
+    #[test]
+    fn verified_collection_reads_past_optional_fields() {
+        let mint = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        let address = metadata_address(&mint);
+
+        for (creators, edition_nonce) in [(0, None), (3, Some(254))] {
+            let data = metadata(&mint, creators, edition_nonce, Some((true, collection)));
+            let read = collection_of(address, TOKEN_METADATA_PROGRAM_ID, &mint, data).unwrap();
+            assert_eq!(read, Some(collection));
+        }
+    }
+
+    #[test]
+    fn verified_collection_ignores_unverified_and_missing_collections() {
+        let mint = Pubkey::new_unique();
+        let address = metadata_address(&mint);
+
+        let unverified = metadata(&mint, 1, None, Some((false, Pubkey::new_unique())));
+        assert_eq!(collection_of(address, TOKEN_METADATA_PROGRAM_ID, &mint, unverified).unwrap(), None);
+        let missing = metadata(&mint, 1, None, None);
+        assert_eq!(collection_of(address, TOKEN_METADATA_PROGRAM_ID, &mint, missing).unwrap(), None);
+    }
+
+    #[test]
+    fn verified_collection_rejects_forged_metadata() {
+        let mint = Pubkey::new_unique();
+        let data = metadata(&mint, 0, None, Some((true, Pubkey::new_unique())));
+
+        // Not owned by the metadata program
+        assert!(collection_of(metadata_address(&mint), crate::ID, &mint, data.clone()).is_err());
+        // Metadata of another mint
+        assert!(collection_of(metadata_address(&Pubkey::new_unique()), TOKEN_METADATA_PROGRAM_ID, &mint, data.clone()).is_err());
+        // Truncated
+        assert!(collection_of(metadata_address(&mint), TOKEN_METADATA_PROGRAM_ID, &mint, data[..data.len() - 1].to_vec()).is_err());
+    }
+}
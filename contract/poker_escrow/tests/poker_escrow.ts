@@ -1,6 +1,6 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL, Ed25519Program, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
 import { PokerEscrow } from "../target/types/poker_escrow";
 import { expect } from "chai";
 
@@ -12,6 +12,7 @@ describe("Enhanced Poker Escrow Tests", () => {
 
   // Test accounts
   let gameServer: Keypair;
+  let serverProfilePda: PublicKey;
   let player1: Keypair;
   let player2: Keypair;
 
@@ -20,11 +21,164 @@ describe("Enhanced Poker Escrow Tests", () => {
   const minPlayers = 2;
   const maxPlayers = 6;
   const handIdentifier = "hand_001_preflop";
+  const publicTable = { access: { public: {} }, tokenGate: null, requiredAttester: null, requiredAttestationFlags: 0 };
 
   const GameType = {
     SingleHand: { singleHand: {} },
+    Tournament: { tournament: {} },
+    CashGame: { cashGame: {} },
+    KnockoutTournament: { knockoutTournament: {} },
   };
 
+  // Protocol config: 1% fee paid to the provider wallet
+  const treasury = provider.publicKey;
+  const feeBps = 100;
+  const serverBond = new anchor.BN(LAMPORTS_PER_SOL);
+
+  const profilePda = (player: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("player_profile"), player.toBuffer()],
+      program.programId
+    )[0];
+
+  const ratingPda = (player: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("rating"), player.toBuffer()],
+      program.programId
+    )[0];
+
+  const settlementPda = (pokerEscrow: PublicKey, handIndex: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("settlement"), pokerEscrow.toBuffer(), new anchor.BN(handIndex).toArrayLike(Buffer, "le", 4)],
+      program.programId
+    )[0];
+
+  const seasonPda = (seasonId: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("season"), new anchor.BN(seasonId).toArrayLike(Buffer, "le", 4)],
+      program.programId
+    )[0];
+
+  const seasonEntryPda = (season: PublicKey, player: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("season_entry"), season.toBuffer(), player.toBuffer()],
+      program.programId
+    )[0];
+
+  // Each game below gets a fresh id and escrow
+  let nextGameId = Date.now() + 100;
+  function newGame() {
+    const gameId = new anchor.BN(nextGameId++);
+    const [pokerEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("poker_game"), serverProfilePda.toBuffer(), gameId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    return { gameId, pokerEscrow };
+  }
+
+  // Create a game, sending `setters` in the same transaction
+  async function createGame(
+    gameId: anchor.BN,
+    gameType: object,
+    setters: anchor.web3.TransactionInstruction[] = [],
+    rules: object = publicTable
+  ) {
+    await program.methods
+      .createGame(gameId, buyInAmount, minPlayers, maxPlayers, gameType as any, handIdentifier, rules as any)
+      .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda })
+      .postInstructions(setters)
+      .signers([gameServer])
+      .rpc();
+  }
+
+  async function joinGame(pokerEscrow: PublicKey, player: Keypair) {
+    await program.methods
+      .joinGame(null, null)
+      .accounts({
+        authority: player.publicKey,
+        player: player.publicKey,
+        pokerEscrow,
+        gameServer: serverProfilePda,
+      })
+      .signers([player])
+      .rpc();
+  }
+
+  async function startGame(pokerEscrow: PublicKey) {
+    await program.methods
+      .startGame(null)
+      .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+      .signers([gameServer])
+      .rpc();
+  }
+
+  // Pay `amount` of the pot to `winner`, at their wallet unless they bought
+  // in from elsewhere
+  function distributePot(
+    pokerEscrow: PublicKey,
+    winner: PublicKey,
+    amount: anchor.BN,
+    remainingAccounts: anchor.web3.AccountMeta[] = [],
+    payoutAccount: PublicKey = winner
+  ) {
+    return program.methods
+      .distributePot(amount, 6, "Full House")
+      .accounts({
+        winner,
+        payoutAccount,
+        pokerEscrow,
+        operator: gameServer.publicKey,
+        gameServer: serverProfilePda,
+        serverAuthority: gameServer.publicKey,
+        treasury,
+        season: null,
+        seasonEntry: null,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([gameServer]);
+  }
+
+  // Payout account, profile and season entry of each winner paid through a
+  // payout list, the program id standing in for players not in `season`
+  const winnerAccounts = (players: Keypair[], season?: PublicKey) =>
+    players.flatMap((p) => [
+      { pubkey: p.publicKey, isSigner: false, isWritable: true },
+      { pubkey: profilePda(p.publicKey), isSigner: false, isWritable: true },
+      season
+        ? { pubkey: seasonEntryPda(season, p.publicKey), isSigner: false, isWritable: true }
+        : { pubkey: program.programId, isSigner: false, isWritable: false },
+    ]);
+
+  // Wallet and profile of each player refunded, for those who joined from their wallet
+  const refundAccounts = (players: Keypair[]) =>
+    players.flatMap((p) => [
+      { pubkey: p.publicKey, isSigner: false, isWritable: true },
+      { pubkey: profilePda(p.publicKey), isSigner: false, isWritable: true },
+    ]);
+
+  const ratingAccounts = (players: Keypair[]) =>
+    players.map((p) => ({ pubkey: ratingPda(p.publicKey), isSigner: false, isWritable: true }));
+
+  // Flip the emergency pause as the guardian, the admin until it is rotated
+  function setPaused(paused: boolean, guardian?: Keypair) {
+    return program.methods
+      .setPaused(paused)
+      .accounts({ authority: guardian ? guardian.publicKey : provider.publicKey })
+      .signers(guardian ? [guardian] : [])
+      .rpc();
+  }
+
+  // Expect `promise` to fail with the program error `code`
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+    } catch (error) {
+      expect(error.message).to.include(code);
+      return;
+    }
+    expect.fail(`Expected ${code}`);
+  }
+
   // Helper function to airdrop
   async function airdrop(keypair: Keypair, amount: number) {
     const conn = provider.connection;
@@ -48,6 +202,44 @@ describe("Enhanced Poker Escrow Tests", () => {
         airdrop(player1, 10 * LAMPORTS_PER_SOL),
         airdrop(player2, 10 * LAMPORTS_PER_SOL),
     ]);
+
+    // The provider wallet deployed the program, so it is the upgrade authority
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      .initializeConfig(
+        treasury,
+        feeBps,
+        [
+          GameType.SingleHand,
+          GameType.Tournament,
+          GameType.CashGame,
+          GameType.KnockoutTournament,
+        ],
+        new anchor.BN(100 * LAMPORTS_PER_SOL),
+        serverBond,
+        provider.publicKey
+      )
+      .accounts({
+        admin: provider.publicKey,
+        programData,
+      })
+      .rpc();
+
+    // Game servers must be bonded before hosting games
+    await program.methods
+      .registerGameServer(serverBond)
+      .accounts({ authority: gameServer.publicKey })
+      .signers([gameServer])
+      .rpc();
+
+    // Escrows are seeded by the server profile, not by its signing key
+    [serverProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("game_server"), gameServer.publicKey.toBuffer()],
+      program.programId
+    );
   });
 
   describe("Game Creation, Flow, and Distribution", () => {
@@ -56,14 +248,15 @@ describe("Enhanced Poker Escrow Tests", () => {
 
     it("Creates a new SingleHand poker game successfully", async () => {
       [pokerEscrowPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poker_game"), gameServer.publicKey.toBuffer(), gameId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("poker_game"), serverProfilePda.toBuffer(), gameId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       await program.methods
-        .createGame(gameId, buyInAmount, minPlayers, maxPlayers, GameType.SingleHand, handIdentifier)
+        .createGame(gameId, buyInAmount, minPlayers, maxPlayers, GameType.SingleHand, handIdentifier, publicTable)
         .accounts({
-          gameServer: gameServer.publicKey,
+          operator: gameServer.publicKey,
+          gameServer: serverProfilePda,
           // `pokerEscrow` is correctly omitted here because it's being created (`init`)
           // and Anchor can derive its address from the instruction arguments.
         })
@@ -76,34 +269,43 @@ describe("Enhanced Poker Escrow Tests", () => {
 
     it("Allows players to join the game", async () => {
       await program.methods
-        .joinGame()
+        .joinGame(null, null)
         .accounts({
+          authority: player1.publicKey,
           player: player1.publicKey,
           pokerEscrow: pokerEscrowPda,
-          gameServer: gameServer.publicKey, // Required for the `has_one` client-side check
+          gameServer: serverProfilePda, // Required for the `has_one` client-side check
         })
         .signers([player1])
         .rpc();
 
       await program.methods
-        .joinGame()
+        .joinGame(3, null)
         .accounts({
+          authority: player2.publicKey,
           player: player2.publicKey,
           pokerEscrow: pokerEscrowPda,
-          gameServer: gameServer.publicKey, // Required for the `has_one` client-side check
+          gameServer: serverProfilePda, // Required for the `has_one` client-side check
         })
         .signers([player2])
         .rpc();
         
       const gameState = await program.account.pokerEscrow.fetch(pokerEscrowPda);
       expect(gameState.currentPlayers).to.equal(2);
+      expect(gameState.players.map((p) => p.seat)).to.deep.equal([0, 3]);
+
+      // Profiles are created on first join
+      const profile = await program.account.playerProfile.fetch(profilePda(player1.publicKey));
+      expect(profile.gamesPlayed.toNumber()).to.equal(1);
+      expect(profile.totalBuyIns.toString()).to.equal(buyInAmount.toString());
     });
     
     it("Starts the game", async () => {
         await program.methods
             .startGame(null)
             .accounts({
-                gameServer: gameServer.publicKey,
+                operator: gameServer.publicKey,
+                gameServer: serverProfilePda,
                 pokerEscrow: pokerEscrowPda,
             })
             .signers([gameServer])
@@ -111,6 +313,8 @@ describe("Enhanced Poker Escrow Tests", () => {
 
         const gameState = await program.account.pokerEscrow.fetch(pokerEscrowPda);
         expect(gameState.status).to.deep.equal({ active: {} });
+        expect(gameState.dealerSeedSlot.toNumber()).to.be.greaterThan(0);
+        expect([0, 3]).to.include(gameState.dealerPosition);
     });
 
     it("Distributes pot and closes the SingleHand game", async () => {
@@ -121,12 +325,34 @@ describe("Enhanced Poker Escrow Tests", () => {
             .distributePot(totalPot, 6, "Full House")
             .accounts({
                 winner: player1.publicKey,
+                payoutAccount: player1.publicKey, // Joined from their wallet
                 pokerEscrow: pokerEscrowPda,
-                gameServer: gameServer.publicKey, // Required for the `has_one` client-side check
+                operator: gameServer.publicKey,
+                gameServer: serverProfilePda, // Required for the `has_one` client-side check
+                serverAuthority: gameServer.publicKey,
+                treasury,
+                season: null, // No season running
+                seasonEntry: null,
             })
+            // Completing a SingleHand game rates everyone at the table
+            .remainingAccounts([player1, player2].map((p) => ({
+                pubkey: ratingPda(p.publicKey),
+                isSigner: false,
+                isWritable: true,
+            })))
             .signers([gameServer])
             .rpc();
 
+        const [winnerRating, loserRating] = await Promise.all(
+            [player1, player2].map((p) => program.account.playerRating.fetch(ratingPda(p.publicKey)))
+        );
+        expect(winnerRating.rating).to.equal(1216);
+        expect(loserRating.rating).to.equal(1184);
+
+        const profile = await program.account.playerProfile.fetch(profilePda(player1.publicKey));
+        expect(profile.handsWon.toNumber()).to.equal(1);
+        expect(profile.biggestPot.toString()).to.equal(totalPot.toString());
+
         // Verify account was closed
         try {
             await program.account.pokerEscrow.fetch(pokerEscrowPda);
@@ -136,4 +362,1803 @@ describe("Enhanced Poker Escrow Tests", () => {
         }
     });
   });
-});
\ No newline at end of file
+
+  describe("Cancelling a pending game", () => {
+    const gameId = new anchor.BN(Date.now() + 1);
+    let pokerEscrowPda: PublicKey;
+
+    it("Refunds every player and closes the escrow", async () => {
+      [pokerEscrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poker_game"), serverProfilePda.toBuffer(), gameId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .createGame(gameId, buyInAmount, minPlayers, maxPlayers, GameType.SingleHand, handIdentifier, publicTable)
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda })
+        .signers([gameServer])
+        .rpc();
+
+      for (const player of [player1, player2]) {
+        await program.methods
+          .joinGame(null, null)
+          .accounts({
+            authority: player.publicKey,
+            player: player.publicKey,
+            pokerEscrow: pokerEscrowPda,
+            gameServer: serverProfilePda,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      const balanceBefore = await provider.connection.getBalance(player2.publicKey);
+
+      await program.methods
+        .cancelGame()
+        .accounts({
+          operator: gameServer.publicKey,
+          gameServer: serverProfilePda,
+          serverAuthority: gameServer.publicKey,
+          pokerEscrow: pokerEscrowPda,
+        })
+        .remainingAccounts([player1, player2].flatMap((p) => [
+          { pubkey: p.publicKey, isSigner: false, isWritable: true },
+          { pubkey: profilePda(p.publicKey), isSigner: false, isWritable: true },
+        ]))
+        .signers([gameServer])
+        .rpc();
+
+      const balanceAfter = await provider.connection.getBalance(player2.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(buyInAmount.toNumber());
+
+      const escrowInfo = await provider.connection.getAccountInfo(pokerEscrowPda);
+      expect(escrowInfo).to.be.null;
+    });
+  });
+
+  describe("Compliance-gated table", () => {
+    const gameId = new anchor.BN(Date.now() + 2);
+    const attester = Keypair.generate();
+    const KYC_VERIFIED = 1;
+    let pokerEscrowPda: PublicKey;
+
+    it("Only lets attested players join", async () => {
+      [pokerEscrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poker_game"), serverProfilePda.toBuffer(), gameId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await airdrop(attester, LAMPORTS_PER_SOL);
+
+      await program.methods
+        .createGame(gameId, buyInAmount, minPlayers, maxPlayers, GameType.SingleHand, handIdentifier, {
+          ...publicTable,
+          requiredAttester: attester.publicKey,
+          requiredAttestationFlags: KYC_VERIFIED,
+        })
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda })
+        .signers([gameServer])
+        .rpc();
+
+      try {
+        await program.methods
+          .joinGame(null, null)
+          .accounts({ authority: player1.publicKey, player: player1.publicKey, pokerEscrow: pokerEscrowPda, gameServer: serverProfilePda })
+          .signers([player1])
+          .rpc();
+        expect.fail("Unattested player should not be able to join");
+      } catch (error) {
+        expect(error.message).to.include("InvalidComplianceAttestation");
+      }
+
+      const [attestationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("compliance"), attester.publicKey.toBuffer(), player1.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .issueAttestation(player1.publicKey, KYC_VERIFIED, new anchor.BN(Math.floor(Date.now() / 1000) + 3600))
+        .accounts({ attester: attester.publicKey })
+        .signers([attester])
+        .rpc();
+
+      await program.methods
+        .joinGame(null, null)
+        .accounts({
+          authority: player1.publicKey,
+          player: player1.publicKey,
+          pokerEscrow: pokerEscrowPda,
+          gameServer: serverProfilePda,
+          complianceAttestation: attestationPda,
+        })
+        .signers([player1])
+        .rpc();
+
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrowPda);
+      expect(gameState.currentPlayers).to.equal(1);
+    });
+  });
+
+  describe("Invite-only tables", () => {
+    const issuer = Keypair.generate();
+    const inviteMessage = (pokerEscrow: PublicKey, player: PublicKey) =>
+      Buffer.concat([Buffer.from("poker_escrow:invite"), pokerEscrow.toBuffer(), player.toBuffer()]);
+    // `signer`'s Ed25519 signature inviting `invitee` to the table
+    const invite = (signer: Keypair, pokerEscrow: PublicKey, invitee: PublicKey) =>
+      Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signer.secretKey,
+        message: inviteMessage(pokerEscrow, invitee),
+      });
+
+    function joinWithInvite(pokerEscrow: PublicKey, player: Keypair, inviteIx: anchor.web3.TransactionInstruction) {
+      return program.methods
+        .joinGame(null, null)
+        .accounts({
+          authority: player.publicKey,
+          player: player.publicKey,
+          pokerEscrow,
+          gameServer: serverProfilePda,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([inviteIx])
+        .signers([player])
+        .rpc();
+    }
+
+    it("Seats only the players the issuer invited", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament, [], { ...publicTable, access: { invite: { issuer: issuer.publicKey } } });
+
+      // Not invited, or invited by someone other than the issuer
+      await expectError(joinGame(pokerEscrow, player2), "TableAccessDenied");
+      await expectError(
+        joinWithInvite(pokerEscrow, player2, invite(player2, pokerEscrow, player2.publicKey)),
+        "TableAccessDenied"
+      );
+
+      await joinWithInvite(pokerEscrow, player1, invite(issuer, pokerEscrow, player1.publicKey));
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.players.map((p) => p.player.toBase58())).to.deep.equal([player1.publicKey.toBase58()]);
+    });
+
+    it("Doesn't let anyone else reuse an invite once it is on chain", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament, [], { ...publicTable, access: { invite: { issuer: issuer.publicKey } } });
+      const player1Invite = invite(issuer, pokerEscrow, player1.publicKey);
+      await joinWithInvite(pokerEscrow, player1, player1Invite);
+
+      // Player 2 replays the signature player 1's join revealed
+      await expectError(joinWithInvite(pokerEscrow, player2, player1Invite), "TableAccessDenied");
+    });
+  });
+
+  describe("Two-phase settlement", () => {
+    // The escrow address depends on the server profile set up in the root hook
+    let pokerEscrow: PublicKey;
+    const disputeBond = new anchor.BN(LAMPORTS_PER_SOL / 10);
+    const settlement = () => settlementPda(pokerEscrow, 0);
+
+    before(async () => {
+      let gameId: anchor.BN;
+      ({ gameId, pokerEscrow } = newGame());
+      const window = await program.methods
+        .setSettlementWindow(new anchor.BN(60), disputeBond)
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+      await createGame(gameId, GameType.Tournament, [window]);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+    });
+
+    it("Locks the game while a settlement is open", async () => {
+      await program.methods
+        .proposeSettlement([{ player: player1.publicKey, amount: buyInAmount }], 5, "Flush")
+        .accounts({
+          operator: gameServer.publicKey,
+          gameServer: serverProfilePda,
+          pokerEscrow,
+          settlement: settlement(),
+        })
+        .signers([gameServer])
+        .rpc();
+
+      // Idle refunds can't pull the pot out from under the settlement
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.status).to.deep.equal({ settling: {} });
+      await expectError(
+        program.methods
+          .emergencyRefund()
+          .accounts({
+            authority: player2.publicKey,
+            player: player2.publicKey,
+            sessionToken: null,
+            gameServer: serverProfilePda,
+            pokerEscrow,
+            payoutAccount: player2.publicKey,
+          })
+          .signers([player2])
+          .rpc(),
+        "RefundTimeoutNotReached"
+      );
+    });
+
+    it("Can't be finalized during the challenge window", async () => {
+      await expectError(
+        program.methods
+          .finalizeSettlement()
+          .accounts({
+            gameServer: serverProfilePda,
+            serverAuthority: gameServer.publicKey,
+            pokerEscrow,
+            settlement: settlement(),
+            treasury,
+            season: null,
+          })
+          .remainingAccounts(winnerAccounts([player1]))
+          .rpc(),
+        "ChallengeWindowOpen"
+      );
+    });
+
+    it("Pays the corrected payouts when a dispute is upheld", async () => {
+      await program.methods
+        .disputeSettlement()
+        .accounts({ player: player2.publicKey, gameServer: serverProfilePda, pokerEscrow, settlement: settlement() })
+        .signers([player2])
+        .rpc();
+      const disputed = await program.account.settlement.fetch(settlement());
+      expect(disputed.status).to.deep.equal({ disputed: {} });
+
+      const balanceBefore = await provider.connection.getBalance(player2.publicKey);
+      const profileBefore = await program.account.playerProfile.fetch(profilePda(player2.publicKey));
+      await program.methods
+        .resolveDispute([{ player: player2.publicKey, amount: buyInAmount }])
+        .accounts({
+          arbiter: provider.publicKey,
+          gameServer: serverProfilePda,
+          serverAuthority: gameServer.publicKey,
+          pokerEscrow,
+          settlement: settlement(),
+          disputer: player2.publicKey,
+          treasury,
+          season: null,
+        })
+        .remainingAccounts(winnerAccounts([player2]))
+        .rpc();
+
+      // Bond back plus the payout less the 1% fee
+      const balanceAfter = await provider.connection.getBalance(player2.publicKey);
+      const fee = buyInAmount.toNumber() * feeBps / 10_000;
+      expect(balanceAfter - balanceBefore).to.equal(disputeBond.toNumber() + buyInAmount.toNumber() - fee);
+
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.status).to.deep.equal({ active: {} });
+      expect(gameState.totalPot.toString()).to.equal(buyInAmount.toString());
+      expect(await provider.connection.getAccountInfo(settlement())).to.be.null;
+
+      // Settled winnings count towards the winner's lifetime stats
+      const profile = await program.account.playerProfile.fetch(profilePda(player2.publicKey));
+      expect(profile.handsWon.toNumber()).to.equal(profileBefore.handsWon.toNumber() + 1);
+      expect(profile.totalWinnings.sub(profileBefore.totalWinnings).toNumber()).to.equal(
+        buyInAmount.toNumber() - fee
+      );
+    });
+
+    it("Only settles a SingleHand game for the whole pot", async () => {
+      const { gameId, pokerEscrow: singleHand } = newGame();
+      const window = await program.methods
+        .setSettlementWindow(new anchor.BN(60), disputeBond)
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow: singleHand })
+        .instruction();
+      await createGame(gameId, GameType.SingleHand, [window]);
+      await joinGame(singleHand, player1);
+      await joinGame(singleHand, player2);
+      await startGame(singleHand);
+
+      const propose = (amount: anchor.BN) =>
+        program.methods
+          .proposeSettlement([{ player: player1.publicKey, amount }], 5, "Flush")
+          .accounts({
+            operator: gameServer.publicKey,
+            gameServer: serverProfilePda,
+            pokerEscrow: singleHand,
+            settlement: settlementPda(singleHand, 0),
+          })
+          .signers([gameServer])
+          .rpc();
+
+      // Half the pot could never be finalized, the game closes after one payout
+      await expectError(propose(buyInAmount), "PayoutMismatch");
+      await propose(buyInAmount.muln(2));
+
+      // Nothing to expire while the settlement can still be paid
+      await expectError(
+        program.methods
+          .expireSettlement()
+          .accounts({
+            gameServer: serverProfilePda,
+            serverAuthority: gameServer.publicKey,
+            pokerEscrow: singleHand,
+            settlement: settlementPda(singleHand, 0),
+            disputer: player2.publicKey,
+          })
+          .rpc(),
+        "SettlementNotExpired"
+      );
+
+      await new Promise((resolve) => setTimeout(resolve, 62_000));
+      await program.methods
+        .finalizeSettlement()
+        .accounts({
+          gameServer: serverProfilePda,
+          serverAuthority: gameServer.publicKey,
+          pokerEscrow: singleHand,
+          settlement: settlementPda(singleHand, 0),
+          treasury,
+          season: null,
+        })
+        .remainingAccounts([...winnerAccounts([player1]), ...ratingAccounts([player1, player2])])
+        .rpc();
+      expect(await provider.connection.getAccountInfo(singleHand)).to.be.null;
+    });
+  });
+
+  describe("Co-signed large payouts", () => {
+    const cosigner = Keypair.generate();
+    const largePayout = new anchor.BN(1.5 * LAMPORTS_PER_SOL);
+    const cosignerMeta = { pubkey: cosigner.publicKey, isSigner: true, isWritable: false };
+
+    const payoutSigners = (pokerEscrow: PublicKey) =>
+      program.methods
+        .setPayoutSigners([gameServer.publicKey, cosigner.publicKey], 2, largePayout)
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+
+    it("Counts earlier payouts towards the co-signing limit", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament, [await payoutSigners(pokerEscrow)]);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+
+      // 1 SOL alone stays under the limit...
+      await distributePot(pokerEscrow, player1.publicKey, buyInAmount).rpc();
+
+      // ...but a second one would move 2 SOL in total without a co-signer
+      await expectError(
+        distributePot(pokerEscrow, player2.publicKey, buyInAmount).rpc(),
+        "InsufficientPayoutSigners"
+      );
+
+      await distributePot(pokerEscrow, player2.publicKey, buyInAmount, [
+        ...ratingAccounts([player1, player2]),
+        cosignerMeta,
+      ])
+        .signers([gameServer, cosigner])
+        .rpc();
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.status).to.deep.equal({ completed: {} });
+      expect(gameState.paidOut.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+    });
+
+    it("Requires a SingleHand payout to take the whole pot", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.SingleHand);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+
+      // Otherwise closing the escrow would hand the rest to the server
+      await expectError(
+        distributePot(pokerEscrow, player1.publicKey, buyInAmount, ratingAccounts([player1, player2])).rpc(),
+        "PayoutMismatch"
+      );
+    });
+
+    it("Requires co-signers when proposing a large settlement", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      const window = await program.methods
+        .setSettlementWindow(new anchor.BN(60), new anchor.BN(0))
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+      await createGame(gameId, GameType.Tournament, [await payoutSigners(pokerEscrow), window]);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+
+      const propose = () =>
+        program.methods
+          .proposeSettlement([{ player: player1.publicKey, amount: new anchor.BN(2 * LAMPORTS_PER_SOL) }], 5, "Flush")
+          .accounts({
+            operator: gameServer.publicKey,
+            gameServer: serverProfilePda,
+            pokerEscrow,
+            settlement: settlementPda(pokerEscrow, 0),
+          });
+
+      await expectError(propose().signers([gameServer]).rpc(), "InsufficientPayoutSigners");
+
+      await propose().remainingAccounts([cosignerMeta]).signers([gameServer, cosigner]).rpc();
+      const settlement = await program.account.settlement.fetch(settlementPda(pokerEscrow, 0));
+      expect(settlement.status).to.deep.equal({ proposed: {} });
+    });
+  });
+
+  describe("Refund crank", () => {
+    function crankRefund(pokerEscrow: PublicKey, players: Keypair[]) {
+      return program.methods
+        .crankRefund()
+        .accounts({ cranker: provider.publicKey, gameServer: serverProfilePda, serverAuthority: gameServer.publicKey, pokerEscrow })
+        .remainingAccounts(refundAccounts(players))
+        .rpc();
+    }
+
+    it("Doesn't refund a game still in play", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+
+      await expectError(crankRefund(pokerEscrow, [player1, player2]), "RefundTimeoutNotReached");
+    });
+
+    it("Finishes refunding a cancelled game and closes it", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+
+      // The server refunds a first chunk, then goes away
+      await program.methods
+        .cancelGame()
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow, serverAuthority: gameServer.publicKey })
+        .remainingAccounts(refundAccounts([player1]))
+        .signers([gameServer])
+        .rpc();
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.status).to.deep.equal({ abandoned: {} });
+      expect(gameState.currentPlayers).to.equal(1);
+
+      // Anyone can refund the rest to the recorded accounts
+      const balanceBefore = await provider.connection.getBalance(player2.publicKey);
+      await crankRefund(pokerEscrow, [player2]);
+      const balanceAfter = await provider.connection.getBalance(player2.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(buyInAmount.toNumber());
+      expect(await provider.connection.getAccountInfo(pokerEscrow)).to.be.null;
+    });
+
+    it("Blocks settlements, rulings, backing payouts and slashing", async () => {
+      const withWindow = async () => {
+        const { gameId, pokerEscrow } = newGame();
+        const window = await program.methods
+          .setSettlementWindow(new anchor.BN(60), new anchor.BN(0))
+          .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+          .instruction();
+        await createGame(gameId, GameType.Tournament, [window]);
+        await joinGame(pokerEscrow, player1);
+        await joinGame(pokerEscrow, player2);
+        await startGame(pokerEscrow);
+        return pokerEscrow;
+      };
+      const propose = (pokerEscrow: PublicKey) =>
+        program.methods
+          .proposeSettlement([{ player: player1.publicKey, amount: buyInAmount }], 5, "Flush")
+          .accounts({
+            operator: gameServer.publicKey,
+            gameServer: serverProfilePda,
+            pokerEscrow,
+            settlement: settlementPda(pokerEscrow, 0),
+          })
+          .signers([gameServer])
+          .rpc();
+
+      // One game with a settlement open, one about to propose
+      const settling = await withWindow();
+      await propose(settling);
+      const proposing = await withWindow();
+
+      // Player 1 backed in full by player 2 and seated
+      const backed = newGame();
+      await createGame(backed.gameId, GameType.Tournament);
+      const [backing] = PublicKey.findProgramAddressSync(
+        [Buffer.from("backing"), backed.pokerEscrow.toBuffer(), player1.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .createBacking([{ backer: player2.publicKey, amount: buyInAmount, shareBps: 1000 }])
+        .accounts({ player: player1.publicKey, pokerEscrow: backed.pokerEscrow })
+        .signers([player1])
+        .rpc();
+      await program.methods.fundBacking().accounts({ backer: player2.publicKey, backing }).signers([player2]).rpc();
+      await program.methods
+        .joinGameBacked(null, null)
+        .accounts({
+          authority: player1.publicKey,
+          player: player1.publicKey,
+          pokerEscrow: backed.pokerEscrow,
+          gameServer: serverProfilePda,
+          backing,
+        })
+        .signers([player1])
+        .rpc();
+
+      await setPaused(true);
+      await expectError(propose(proposing), "ProgramPaused");
+      await expectError(
+        program.methods
+          .resolveDispute(null)
+          .accounts({
+            arbiter: provider.publicKey,
+            gameServer: serverProfilePda,
+            serverAuthority: gameServer.publicKey,
+            pokerEscrow: settling,
+            settlement: settlementPda(settling, 0),
+            disputer: player2.publicKey,
+            treasury,
+            season: null,
+          })
+          .remainingAccounts(winnerAccounts([player1]))
+          .rpc(),
+        "ProgramPaused"
+      );
+      await expectError(
+        program.methods
+          .settleBacking()
+          .accounts({ authority: player1.publicKey, player: player1.publicKey, backing, pokerEscrow: backed.pokerEscrow })
+          .remainingAccounts([{ pubkey: player2.publicKey, isSigner: false, isWritable: true }])
+          .signers([player1])
+          .rpc(),
+        "ProgramPaused"
+      );
+      await expectError(
+        program.methods
+          .slashGameServer([new anchor.BN(1)])
+          .accounts({ arbiter: provider.publicKey, gameServerProfile: serverProfilePda, pokerEscrow: backed.pokerEscrow })
+          .remainingAccounts([{ pubkey: player1.publicKey, isSigner: false, isWritable: true }])
+          .rpc(),
+        "ProgramPaused"
+      );
+    });
+  });
+
+  describe("Token-gated tables", () => {
+    // SPL Token instructions built by hand: InitializeMint2, InitializeAccount3, MintTo
+    const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+    const mint = Keypair.generate();
+    const tokenAccount = Keypair.generate();
+
+    async function createTokenAccount(space: number, account: Keypair, init: Buffer, keys: PublicKey[]) {
+      const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+      const tx = new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: provider.publicKey,
+          newAccountPubkey: account.publicKey,
+          lamports,
+          space,
+          programId: TOKEN_PROGRAM_ID,
+        }),
+        new anchor.web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: keys.map((pubkey, i) => ({ pubkey, isSigner: false, isWritable: i === 0 })),
+          data: init,
+        })
+      );
+      await provider.sendAndConfirm(tx, [account]);
+    }
+
+    async function mintTo(amount: number) {
+      const tx = new anchor.web3.Transaction().add(
+        new anchor.web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: [
+            { pubkey: mint.publicKey, isSigner: false, isWritable: true },
+            { pubkey: tokenAccount.publicKey, isSigner: false, isWritable: true },
+            { pubkey: provider.publicKey, isSigner: true, isWritable: false },
+          ],
+          data: Buffer.concat([Buffer.from([7]), new anchor.BN(amount).toArrayLike(Buffer, "le", 8)]),
+        })
+      );
+      await provider.sendAndConfirm(tx);
+    }
+
+    function joinGated(pokerEscrow: PublicKey, player: Keypair, gateTokenAccount: PublicKey | null) {
+      return program.methods
+        .joinGame(null, null)
+        .accounts({
+          authority: player.publicKey,
+          player: player.publicKey,
+          pokerEscrow,
+          gameServer: serverProfilePda,
+          gateTokenAccount,
+        })
+        .signers([player])
+        .rpc();
+    }
+
+    before(async () => {
+      // 0 decimals, minted by the provider, no freeze authority
+      await createTokenAccount(
+        82,
+        mint,
+        Buffer.concat([Buffer.from([20, 0]), provider.publicKey.toBuffer(), Buffer.from([0])]),
+        [mint.publicKey]
+      );
+      await createTokenAccount(
+        165,
+        tokenAccount,
+        Buffer.concat([Buffer.from([18]), player1.publicKey.toBuffer()]),
+        [tokenAccount.publicKey, mint.publicKey]
+      );
+      await mintTo(3);
+    });
+
+    it("Only seats players holding enough of the gate token", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament, [], {
+        ...publicTable,
+        tokenGate: { mint: { mint: mint.publicKey, minAmount: new anchor.BN(5) } },
+      });
+
+      // No token account at all, or someone else's
+      await expectError(joinGated(pokerEscrow, player2, null), "TokenGateNotMet");
+      await expectError(joinGated(pokerEscrow, player2, tokenAccount.publicKey), "TokenGateNotMet");
+
+      // 3 of the 5 tokens required
+      await expectError(joinGated(pokerEscrow, player1, tokenAccount.publicKey), "TokenGateNotMet");
+
+      await mintTo(2);
+      await joinGated(pokerEscrow, player1, tokenAccount.publicKey);
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.currentPlayers).to.equal(1);
+    });
+  });
+
+  describe("Responsible gaming limits", () => {
+    // Limits outlive any one game, so these wallets are used here only
+    const capped = Keypair.generate();
+    const excluded = Keypair.generate();
+
+    function setLimits(player: Keypair, dailyBuyInCap: number, excludedUntil: number) {
+      return program.methods
+        .setPlayerLimits({
+          dailyBuyInCap: new anchor.BN(dailyBuyInCap),
+          weeklyBuyInCap: new anchor.BN(0),
+          excludedUntil: new anchor.BN(excludedUntil),
+        })
+        .accounts({ player: player.publicKey })
+        .signers([player])
+        .rpc();
+    }
+
+    before(async () => {
+      await Promise.all([
+        airdrop(capped, 2 * LAMPORTS_PER_SOL),
+        airdrop(excluded, 2 * LAMPORTS_PER_SOL),
+      ]);
+    });
+
+    it("Blocks buy-ins over the daily cap, and delays raising it", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+
+      await setLimits(capped, buyInAmount.toNumber() / 2, 0);
+      await expectError(joinGame(pokerEscrow, capped), "BuyInLimitExceeded");
+
+      // Lifting the cap only takes effect after the cooling-off period
+      await setLimits(capped, 0, 0);
+      const [limitsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("player_limits"), capped.publicKey.toBuffer()],
+        program.programId
+      );
+      const limits = await program.account.playerLimits.fetch(limitsPda);
+      expect(limits.settings.dailyBuyInCap.toNumber()).to.equal(buyInAmount.toNumber() / 2);
+      expect(limits.pending.dailyBuyInCap.toNumber()).to.equal(0);
+      await expectError(joinGame(pokerEscrow, capped), "BuyInLimitExceeded");
+    });
+
+    it("Keeps self-excluded players out of every game", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+
+      await setLimits(excluded, 0, Math.floor(Date.now() / 1000) + 3600);
+      await expectError(joinGame(pokerEscrow, excluded), "SelfExcluded");
+    });
+  });
+
+  describe("Emergency pause", () => {
+    afterEach(async () => {
+      await setPaused(false);
+    });
+
+    it("Blocks new games and deposits but not refunds", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      await joinGame(pokerEscrow, player1);
+
+      await setPaused(true);
+      const next = newGame();
+      await expectError(createGame(next.gameId, GameType.Tournament), "ProgramPaused");
+      await expectError(joinGame(pokerEscrow, player2), "ProgramPaused");
+
+      // Players can still get their deposits back
+      const balanceBefore = await provider.connection.getBalance(player1.publicKey);
+      await program.methods
+        .cancelGame()
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow, serverAuthority: gameServer.publicKey })
+        .remainingAccounts(refundAccounts([player1]))
+        .signers([gameServer])
+        .rpc();
+      const balanceAfter = await provider.connection.getBalance(player1.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(buyInAmount.toNumber());
+      expect(await provider.connection.getAccountInfo(pokerEscrow)).to.be.null;
+    });
+  });
+
+  describe("Idle refunds", () => {
+    const idleTimeout = (pokerEscrow: PublicKey, seconds: number) =>
+      program.methods
+        .setIdleTimeout(new anchor.BN(seconds))
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+
+    it("Keeps the idle timeout within bounds", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await expectError(
+        createGame(gameId, GameType.CashGame, [await idleTimeout(pokerEscrow, 60)]),
+        "InvalidIdleTimeout"
+      );
+    });
+
+    it("Doesn't refund an active game before it goes idle", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.CashGame, [await idleTimeout(pokerEscrow, 60 * 60)]);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+
+      await expectError(
+        program.methods
+          .emergencyRefund()
+          .accounts({
+            authority: player1.publicKey,
+            player: player1.publicKey,
+            sessionToken: null,
+            gameServer: serverProfilePda,
+            pokerEscrow,
+            payoutAccount: player1.publicKey,
+          })
+          .signers([player1])
+          .rpc(),
+        "RefundTimeoutNotReached"
+      );
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.idleTimeout.toNumber()).to.equal(60 * 60);
+      expect(gameState.lastActivityAt.toNumber()).to.be.greaterThan(0);
+    });
+  });
+
+  describe("Attested payouts", () => {
+    let pokerEscrow: PublicKey;
+    const payouts = [{ player: null as PublicKey, amount: buyInAmount }];
+
+    // Payout list as signed by players and the server, see `Settlement::attestation_message`
+    function attestationMessage(escrow: PublicKey, handIndex: number, handRank: number, description: string) {
+      return Buffer.concat([
+        Buffer.from("poker_escrow:settle"),
+        escrow.toBuffer(),
+        new anchor.BN(handIndex).toArrayLike(Buffer, "le", 4),
+        Buffer.from([handRank, payouts.length]),
+        ...payouts.map((p) => Buffer.concat([p.player.toBuffer(), p.amount.toArrayLike(Buffer, "le", 8)])),
+        Buffer.from([description.length]),
+        Buffer.from(description),
+      ]);
+    }
+
+    // Submitted by the server operator, which counts as its attestation
+    function distributeAttested(signer: Keypair, message: Buffer, escrow = pokerEscrow, cosigners: Keypair[] = []) {
+      return program.methods
+        .distributePotAttested(payouts, 5, "Flush")
+        .accounts({
+          submitter: gameServer.publicKey,
+          gameServer: serverProfilePda,
+          serverAuthority: gameServer.publicKey,
+          pokerEscrow: escrow,
+          treasury,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          season: null,
+        })
+        .remainingAccounts([
+          ...winnerAccounts([player1]),
+          ...cosigners.map((c) => ({ pubkey: c.publicKey, isSigner: true, isWritable: false })),
+        ])
+        .preInstructions([Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message })])
+        .signers([gameServer, ...cosigners])
+        .rpc();
+    }
+
+    // Attested game with extra settings, player 1 and 2 seated
+    async function startAttestedGame(setting: (escrow: PublicKey) => Promise<anchor.web3.TransactionInstruction>) {
+      const { gameId, pokerEscrow: escrow } = newGame();
+      const threshold = await program.methods
+        .setAttestationThreshold(2)
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow: escrow })
+        .instruction();
+      await createGame(gameId, GameType.Tournament, [threshold, await setting(escrow)]);
+      await joinGame(escrow, player1);
+      await joinGame(escrow, player2);
+      await startGame(escrow);
+      return escrow;
+    }
+
+    before(async () => {
+      let gameId: anchor.BN;
+      ({ gameId, pokerEscrow } = newGame());
+      payouts[0].player = player1.publicKey;
+      const threshold = await program.methods
+        .setAttestationThreshold(2)
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+      await createGame(gameId, GameType.Tournament, [threshold]);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+    });
+
+    it("Rejects an attestation for another game", async () => {
+      const { pokerEscrow: otherEscrow } = newGame();
+      await expectError(
+        distributeAttested(player2, attestationMessage(otherEscrow, 0, 5, "Flush")),
+        "InsufficientAttestations"
+      );
+    });
+
+    it("Pays a payout list attested by a player and the server", async () => {
+      const balanceBefore = await provider.connection.getBalance(player1.publicKey);
+      await distributeAttested(player2, attestationMessage(pokerEscrow, 0, 5, "Flush"));
+      const balanceAfter = await provider.connection.getBalance(player1.publicKey);
+      const fee = buyInAmount.toNumber() * feeBps / 10_000;
+      expect(balanceAfter - balanceBefore).to.equal(buyInAmount.toNumber() - fee);
+
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.totalHandsPlayed).to.equal(1);
+    });
+
+    it("Rejects replaying an attestation for an earlier hand", async () => {
+      await expectError(
+        distributeAttested(player2, attestationMessage(pokerEscrow, 0, 5, "Flush")),
+        "InsufficientAttestations"
+      );
+    });
+
+    it("Still requires the payout co-signers for large payouts", async () => {
+      const cosigner = Keypair.generate();
+      const escrow = await startAttestedGame((escrow) =>
+        program.methods
+          .setPayoutSigners([gameServer.publicKey, cosigner.publicKey], 2, buyInAmount)
+          .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow: escrow })
+          .instruction()
+      );
+      const message = attestationMessage(escrow, 0, 5, "Flush");
+
+      await expectError(distributeAttested(player2, message, escrow), "InsufficientPayoutSigners");
+      await distributeAttested(player2, message, escrow, [cosigner]);
+      const gameState = await program.account.pokerEscrow.fetch(escrow);
+      expect(gameState.paidOut.toString()).to.equal(buyInAmount.toString());
+    });
+
+    it("Can't skip a game's dispute window", async () => {
+      const escrow = await startAttestedGame((escrow) =>
+        program.methods
+          .setSettlementWindow(new anchor.BN(60), new anchor.BN(0))
+          .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow: escrow })
+          .instruction()
+      );
+      await expectError(
+        distributeAttested(player2, attestationMessage(escrow, 0, 5, "Flush"), escrow),
+        "SettlementWindowRequired"
+      );
+    });
+  });
+
+  describe("State channels", () => {
+    let pokerEscrow: PublicKey;
+    const sol = (amount: number) => new anchor.BN(Math.round(amount * LAMPORTS_PER_SOL));
+    const channel = () =>
+      PublicKey.findProgramAddressSync([Buffer.from("channel"), pokerEscrow.toBuffer()], program.programId)[0];
+
+    // Balance state as signed off-chain, see `Channel::state_message`
+    function stateMessage(nonce: number, stacks: { player: PublicKey; stack: anchor.BN }[]) {
+      return Buffer.concat([
+        Buffer.from("poker_escrow:channel"),
+        pokerEscrow.toBuffer(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+        Buffer.from([stacks.length]),
+        ...stacks.map((s) => Buffer.concat([s.player.toBuffer(), s.stack.toArrayLike(Buffer, "le", 8)])),
+      ]);
+    }
+
+    // State signed by `signers`, submitted by the server operator
+    function submitState(method: "settleChannel" | "challengeChannel", nonce: number, p1Stack: number, signers: Keypair[]) {
+      const stacks = [
+        { player: player1.publicKey, stack: sol(p1Stack) },
+        { player: player2.publicKey, stack: sol(2).sub(sol(p1Stack)) },
+      ];
+      const message = stateMessage(nonce, stacks);
+      const accounts = {
+        gameServer: serverProfilePda,
+        pokerEscrow,
+        channel: channel(),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      };
+      const builder = method === "settleChannel"
+        ? program.methods.settleChannel(new anchor.BN(nonce), stacks).accounts({ submitter: gameServer.publicKey, ...accounts })
+        : program.methods.challengeChannel(new anchor.BN(nonce), stacks).accounts({ challenger: gameServer.publicKey, ...accounts });
+      return builder
+        .preInstructions(signers.map((s) => Ed25519Program.createInstructionWithPrivateKey({ privateKey: s.secretKey, message })))
+        .signers([gameServer])
+        .rpc();
+    }
+
+    before(async () => {
+      let gameId: anchor.BN;
+      ({ gameId, pokerEscrow } = newGame());
+      const window = await program.methods
+        .setSettlementWindow(new anchor.BN(60), new anchor.BN(0))
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+      await createGame(gameId, GameType.CashGame, [window]);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+    });
+
+    it("Rejects a state not signed by every player", async () => {
+      await expectError(submitState("settleChannel", 5, 1.5, [player1]), "ChannelStateNotFullySigned");
+    });
+
+    it("Only lets newer states replace the submitted one", async () => {
+      await submitState("settleChannel", 5, 1.5, [player1, player2]);
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.status).to.deep.equal({ settling: {} });
+
+      await expectError(submitState("challengeChannel", 5, 1.2, [player1, player2]), "StaleChannelNonce");
+      await expectError(submitState("challengeChannel", 4, 1.2, [player1, player2]), "StaleChannelNonce");
+
+      await submitState("challengeChannel", 6, 1.6, [player1, player2]);
+      const state = await program.account.channel.fetch(channel());
+      expect(state.nonce.toNumber()).to.equal(6);
+      expect(state.stacks[0].stack.toString()).to.equal(sol(1.6).toString());
+    });
+
+    it("Takes the fee only on net winnings", async () => {
+      // Wait out the challenge window restarted by the last challenge
+      await new Promise((resolve) => setTimeout(resolve, 62_000));
+
+      const before = await Promise.all([player1, player2].map((p) => provider.connection.getBalance(p.publicKey)));
+      await program.methods
+        .finalizeChannel()
+        .accounts({
+          gameServer: serverProfilePda,
+          pokerEscrow,
+          channel: channel(),
+          rentPayer: gameServer.publicKey,
+          treasury,
+          season: null,
+        })
+        .remainingAccounts(winnerAccounts([player1, player2]))
+        .rpc();
+      const after = await Promise.all([player1, player2].map((p) => provider.connection.getBalance(p.publicKey)));
+
+      // Player 1 won 0.6 SOL over their buy-in, player 2 lost and pays nothing
+      const fee = 0.6 * LAMPORTS_PER_SOL * feeBps / 10_000;
+      expect(after[0] - before[0]).to.equal(1.6 * LAMPORTS_PER_SOL - fee);
+      expect(after[1] - before[1]).to.equal(0.4 * LAMPORTS_PER_SOL);
+
+      // The whole pot counts towards the co-signing limit
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.status).to.deep.equal({ completed: {} });
+      expect(gameState.paidOut.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+    });
+  });
+
+  describe("Bankroll withdrawals", () => {
+    const bankrollPda = (player: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("bankroll"), player.toBuffer()], program.programId)[0];
+    const withdrawalPda = (source: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("withdrawal"), source.toBuffer()], program.programId)[0];
+
+    it("Holds a withdrawal until it unlocks and lets the player cancel it", async () => {
+      const bankroll = bankrollPda(player2.publicKey);
+      const withdrawalRequest = withdrawalPda(bankroll);
+      await program.methods
+        .deposit(new anchor.BN(LAMPORTS_PER_SOL / 2))
+        .accounts({ player: player2.publicKey })
+        .signers([player2])
+        .rpc();
+      await program.methods
+        .requestWithdrawal(new anchor.BN(LAMPORTS_PER_SOL / 5))
+        .accounts({ authority: player2.publicKey, player: player2.publicKey, sessionToken: null })
+        .signers([player2])
+        .rpc();
+
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      expect(request.unlocksAt.sub(request.requestedAt).toNumber()).to.equal(24 * 60 * 60);
+      const execute = () =>
+        program.methods
+          .executeWithdrawal()
+          .accounts({ player: player2.publicKey, bankroll, withdrawalRequest, requestedBy: player2.publicKey })
+          .rpc();
+      await expectError(execute(), "WithdrawalLocked");
+
+      // Nothing leaves the bankroll while the program is paused
+      await setPaused(true);
+      await expectError(execute(), "ProgramPaused");
+      await setPaused(false);
+
+      await program.methods
+        .cancelWithdrawal()
+        .accounts({ player: player2.publicKey, withdrawalRequest, requestedBy: player2.publicKey })
+        .signers([player2])
+        .rpc();
+      expect(await provider.connection.getAccountInfo(withdrawalRequest)).to.be.null;
+
+      // Nothing left to execute once cancelled
+      await expectError(execute(), "AccountNotInitialized");
+    });
+
+    it("Credits winnings from a bankroll buy-in back to the bankroll", async () => {
+      const bankroll = bankrollPda(player1.publicKey);
+      await program.methods
+        .deposit(buyInAmount)
+        .accounts({ player: player1.publicKey })
+        .signers([player1])
+        .rpc();
+
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      await program.methods
+        .joinGameFromBankroll(null, null)
+        .accounts({
+          authority: player1.publicKey,
+          player: player1.publicKey,
+          pokerEscrow,
+          gameServer: serverProfilePda,
+          bankroll,
+        })
+        .signers([player1])
+        .rpc();
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.players[0].payoutTo.toBase58()).to.equal(bankroll.toBase58());
+
+      // Player 1 wins the whole pot, paid to the bankroll rather than their wallet
+      const [bankrollBefore, walletBefore] = await Promise.all([
+        provider.connection.getBalance(bankroll),
+        provider.connection.getBalance(player1.publicKey),
+      ]);
+      const pot = new anchor.BN(2 * LAMPORTS_PER_SOL);
+      await distributePot(pokerEscrow, player1.publicKey, pot, ratingAccounts([player1, player2]), bankroll).rpc();
+
+      const fee = pot.muln(feeBps).divn(10_000);
+      expect(await provider.connection.getBalance(bankroll)).to.equal(bankrollBefore + pot.sub(fee).toNumber());
+      expect(await provider.connection.getBalance(player1.publicKey)).to.equal(walletBefore);
+    });
+  });
+
+  describe("Session keys", () => {
+    const bankroll = () =>
+      PublicKey.findProgramAddressSync([Buffer.from("bankroll"), player1.publicKey.toBuffer()], program.programId)[0];
+    const JOIN = 1;
+
+    // Session key for player 1 allowed to join tables, spending up to `spendLimit`
+    async function createSession(spendLimit: anchor.BN) {
+      const sessionKey = Keypair.generate();
+      await program.methods
+        .createSession(sessionKey.publicKey, JOIN, spendLimit, new anchor.BN(Math.floor(Date.now() / 1000) + 3600))
+        .accounts({ player: player1.publicKey })
+        .signers([player1])
+        .rpc();
+      const [sessionToken] = PublicKey.findProgramAddressSync(
+        [Buffer.from("session"), player1.publicKey.toBuffer(), sessionKey.publicKey.toBuffer()],
+        program.programId
+      );
+      return { sessionKey, sessionToken };
+    }
+
+    function joinWithSession(method: "joinGame" | "joinGameFromBankroll", pokerEscrow: PublicKey, sessionKey: Keypair, sessionToken: PublicKey) {
+      const builder = method === "joinGame" ? program.methods.joinGame(null, null) : program.methods.joinGameFromBankroll(null, null);
+      return builder
+        .accounts({
+          authority: sessionKey.publicKey,
+          player: player1.publicKey,
+          sessionToken,
+          pokerEscrow,
+          gameServer: serverProfilePda,
+          bankroll: method === "joinGame" ? null : bankroll(),
+        })
+        .signers([sessionKey])
+        .rpc();
+    }
+
+    it("Can't spend from the player's wallet", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      const { sessionKey, sessionToken } = await createSession(buyInAmount.muln(5));
+
+      await expectError(joinWithSession("joinGame", pokerEscrow, sessionKey, sessionToken), "WalletSignatureRequired");
+    });
+
+    it("Can't spend more than its spend limit", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      const { sessionKey, sessionToken } = await createSession(buyInAmount.divn(2));
+
+      // The bankroll holds enough for the buy-in, the session may not spend it
+      expect(await provider.connection.getBalance(bankroll())).to.be.greaterThan(buyInAmount.toNumber());
+      await expectError(
+        joinWithSession("joinGameFromBankroll", pokerEscrow, sessionKey, sessionToken),
+        "SessionLimitExceeded"
+      );
+    });
+  });
+
+  describe("Operators and delegates", () => {
+    const START_GAME = 1;
+    const DISTRIBUTE_POT = 2;
+
+    function createGameAs(signer: Keypair) {
+      const game = newGame();
+      const created = program.methods
+        .createGame(game.gameId, buyInAmount, minPlayers, maxPlayers, GameType.Tournament, handIdentifier, publicTable)
+        .accounts({ operator: signer.publicKey, gameServer: serverProfilePda })
+        .signers([signer])
+        .rpc();
+      return { ...game, created };
+    }
+
+    const startAs = (signer: Keypair, pokerEscrow: PublicKey) =>
+      program.methods
+        .startGame(null)
+        .accounts({ operator: signer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .signers([signer])
+        .rpc();
+
+    it("Rotates the operator key, cutting off the previous one", async () => {
+      const [first, second] = [Keypair.generate(), Keypair.generate()];
+      await Promise.all([airdrop(first, LAMPORTS_PER_SOL), airdrop(second, LAMPORTS_PER_SOL)]);
+      const setOperator = (operator: PublicKey, authority: Keypair = gameServer) =>
+        program.methods
+          .setOperator(operator)
+          .accounts({ authority: authority.publicKey, gameServer: serverProfilePda })
+          .signers([authority])
+          .rpc();
+
+      // Only the server's authority can rotate it
+      await expectError(setOperator(first.publicKey, player1), "UnauthorizedGameServer");
+
+      await setOperator(first.publicKey);
+      await createGameAs(first).created;
+
+      await setOperator(second.publicKey);
+      await expectError(createGameAs(first).created, "UnauthorizedGameServer");
+      await createGameAs(second).created;
+      const profile = await program.account.gameServerProfile.fetch(serverProfilePda);
+      expect(profile.operator.toBase58()).to.equal(second.publicKey.toBase58());
+    });
+
+    it("Limits a delegate to starting games and paying out pots", async () => {
+      const delegate = Keypair.generate();
+      await airdrop(delegate, LAMPORTS_PER_SOL);
+      const setDelegate = (permissions: number, authority: Keypair = gameServer) =>
+        program.methods
+          .setDelegate(delegate.publicKey, permissions)
+          .accounts({ authority: authority.publicKey, gameServer: serverProfilePda })
+          .signers([authority])
+          .rpc();
+
+      await expectError(setDelegate(START_GAME | DISTRIBUTE_POT, player1), "UnauthorizedGameServer");
+      await setDelegate(START_GAME | DISTRIBUTE_POT);
+
+      // Creating games stays with the operator
+      await expectError(createGameAs(delegate).created, "UnauthorizedGameServer");
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startAs(delegate, pokerEscrow);
+
+      // Seats, slashing and the bond are out of its reach
+      await expectError(
+        program.methods
+          .setSeatStatus(player2.publicKey, { sittingOut: {} })
+          .accounts({ operator: delegate.publicKey, gameServer: serverProfilePda, pokerEscrow })
+          .signers([delegate])
+          .rpc(),
+        "UnauthorizedGameServer"
+      );
+      await expectError(
+        program.methods
+          .slashGameServer([new anchor.BN(1)])
+          .accounts({ arbiter: delegate.publicKey, gameServerProfile: serverProfilePda, pokerEscrow })
+          .remainingAccounts([{ pubkey: player1.publicKey, isSigner: false, isWritable: true }])
+          .signers([delegate])
+          .rpc(),
+        "UnauthorizedArbiter"
+      );
+      await expectError(
+        program.methods
+          .topUpBond(new anchor.BN(1))
+          .accounts({ authority: delegate.publicKey, gameServerProfile: serverProfilePda })
+          .signers([delegate])
+          .rpc(),
+        "UnauthorizedGameServer"
+      );
+
+      const balanceBefore = await provider.connection.getBalance(player1.publicKey);
+      await program.methods
+        .distributePot(new anchor.BN(2 * LAMPORTS_PER_SOL), 6, "Full House")
+        .accounts({
+          winner: player1.publicKey,
+          payoutAccount: player1.publicKey,
+          pokerEscrow,
+          operator: delegate.publicKey,
+          gameServer: serverProfilePda,
+          serverAuthority: gameServer.publicKey,
+          treasury,
+          season: null,
+          seasonEntry: null,
+        })
+        .remainingAccounts(ratingAccounts([player1, player2]))
+        .signers([delegate])
+        .rpc();
+      const fee = 2 * LAMPORTS_PER_SOL * feeBps / 10_000;
+      expect(await provider.connection.getBalance(player1.publicKey)).to.equal(balanceBefore + 2 * LAMPORTS_PER_SOL - fee);
+
+      // Revoked, it can't start the next game
+      await setDelegate(0);
+      const next = newGame();
+      await createGame(next.gameId, GameType.Tournament);
+      await joinGame(next.pokerEscrow, player1);
+      await joinGame(next.pokerEscrow, player2);
+      await expectError(startAs(delegate, next.pokerEscrow), "UnauthorizedGameServer");
+      await startGame(next.pokerEscrow);
+    });
+  });
+
+  describe("Backed buy-ins", () => {
+    const backer1 = Keypair.generate();
+    const backer2 = Keypair.generate();
+    // 60% and 40% of the 1 SOL buy-in, for 30% and 20% of the profit
+    const terms = () => [
+      { backer: backer1.publicKey, amount: new anchor.BN(0.6 * LAMPORTS_PER_SOL), shareBps: 3000 },
+      { backer: backer2.publicKey, amount: new anchor.BN(0.4 * LAMPORTS_PER_SOL), shareBps: 2000 },
+    ];
+    const backerWallets = [backer1, backer2].map((b) => ({ pubkey: b.publicKey, isSigner: false, isWritable: true }));
+    const fee = (amount: number) => amount * feeBps / 10_000;
+
+    const backingPda = (pokerEscrow: PublicKey, player: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("backing"), pokerEscrow.toBuffer(), player.toBuffer()],
+        program.programId
+      )[0];
+
+    before(async () => {
+      await Promise.all([airdrop(backer1, 2 * LAMPORTS_PER_SOL), airdrop(backer2, 2 * LAMPORTS_PER_SOL)]);
+    });
+
+    // Player 1 backed by both backers, player 2 from their wallet
+    async function startBackedGame(funders: Keypair[] = [backer1, backer2]) {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      const backing = backingPda(pokerEscrow, player1.publicKey);
+      await program.methods
+        .createBacking(terms())
+        .accounts({ player: player1.publicKey, pokerEscrow })
+        .signers([player1])
+        .rpc();
+      for (const backer of funders) {
+        await program.methods.fundBacking().accounts({ backer: backer.publicKey, backing }).signers([backer]).rpc();
+      }
+      return { pokerEscrow, backing };
+    }
+
+    async function joinBacked(pokerEscrow: PublicKey, backing: PublicKey) {
+      await program.methods
+        .joinGameBacked(null, null)
+        .accounts({
+          authority: player1.publicKey,
+          player: player1.publicKey,
+          pokerEscrow,
+          gameServer: serverProfilePda,
+          backing,
+        })
+        .signers([player1])
+        .rpc();
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+    }
+
+    // Settle the backing, returning what each backer received
+    async function settle(pokerEscrow: PublicKey, backing: PublicKey, authority: Keypair = player1) {
+      const before = await Promise.all([backer1, backer2].map((b) => provider.connection.getBalance(b.publicKey)));
+      await program.methods
+        .settleBacking()
+        .accounts({ authority: authority.publicKey, player: player1.publicKey, backing, pokerEscrow })
+        .remainingAccounts(backerWallets)
+        .signers([authority])
+        .rpc();
+      const after = await Promise.all([backer1, backer2].map((b) => provider.connection.getBalance(b.publicKey)));
+      return after.map((balance, i) => balance - before[i]);
+    }
+
+    it("Returns stakes before splitting the profit", async () => {
+      const { pokerEscrow, backing } = await startBackedGame();
+      await joinBacked(pokerEscrow, backing);
+
+      // Player 1 wins the whole 2 SOL pot into the backing
+      await distributePot(
+        pokerEscrow,
+        player1.publicKey,
+        new anchor.BN(2 * LAMPORTS_PER_SOL),
+        ratingAccounts([player1, player2]),
+        backing
+      ).rpc();
+
+      // 1.98 SOL received: stakes back, then 30% and 20% of the 0.98 SOL profit
+      const received = 2 * LAMPORTS_PER_SOL - fee(2 * LAMPORTS_PER_SOL);
+      const profit = received - LAMPORTS_PER_SOL;
+      const paid = await settle(pokerEscrow, backing);
+      expect(paid).to.deep.equal([
+        0.6 * LAMPORTS_PER_SOL + Math.floor(profit * 3000 / 10_000),
+        0.4 * LAMPORTS_PER_SOL + Math.floor(profit * 2000 / 10_000),
+      ]);
+      expect(await provider.connection.getAccountInfo(backing)).to.be.null;
+    });
+
+    it("Returns what is left of the stakes proportionally after a loss", async () => {
+      const { pokerEscrow, backing } = await startBackedGame();
+      await joinBacked(pokerEscrow, backing);
+
+      // Player 1 only gets 0.5 SOL of the pot back
+      await distributePot(pokerEscrow, player1.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 2), [], backing).rpc();
+
+      // Still in the game, so nobody can settle yet
+      await expectError(settle(pokerEscrow, backing), "BackingNotSettleable");
+
+      await distributePot(
+        pokerEscrow,
+        player2.publicKey,
+        new anchor.BN(1.5 * LAMPORTS_PER_SOL),
+        ratingAccounts([player1, player2])
+      ).rpc();
+
+      const received = LAMPORTS_PER_SOL / 2 - fee(LAMPORTS_PER_SOL / 2);
+      const paid = await settle(pokerEscrow, backing, backer1);
+      expect(paid[0]).to.be.closeTo(received * 0.6, 1);
+      expect(paid[1]).to.be.closeTo(received * 0.4, 1);
+    });
+
+    it("Lets the player call off a backing before joining", async () => {
+      const { pokerEscrow, backing } = await startBackedGame([backer1]);
+
+      // Only the player can, while the game is still open
+      await expectError(settle(pokerEscrow, backing, backer1), "BackingNotSettleable");
+
+      const paid = await settle(pokerEscrow, backing);
+      expect(paid).to.deep.equal([0.6 * LAMPORTS_PER_SOL, 0]);
+    });
+  });
+
+  describe("Slashing", () => {
+    const compensation = new anchor.BN(LAMPORTS_PER_SOL / 10);
+
+    function slash(pokerEscrow: PublicKey, recipient: PublicKey) {
+      return program.methods
+        .slashGameServer([compensation])
+        .accounts({ arbiter: provider.publicKey, gameServerProfile: serverProfilePda, pokerEscrow })
+        .remainingAccounts([{ pubkey: recipient, isSigner: false, isWritable: true }]);
+    }
+
+    it("Compensates only the players of the slashed game", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      await joinGame(pokerEscrow, player1);
+
+      // The bond can't be paid to anyone outside the game
+      await expectError(slash(pokerEscrow, Keypair.generate().publicKey).rpc(), "InvalidCompensationRecipient");
+      await expectError(slash(pokerEscrow, player2.publicKey).rpc(), "InvalidCompensationRecipient");
+
+      const balanceBefore = await provider.connection.getBalance(player1.publicKey);
+      await slash(pokerEscrow, player1.publicKey).rpc();
+      const balanceAfter = await provider.connection.getBalance(player1.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(compensation.toNumber());
+
+      const profile = await program.account.gameServerProfile.fetch(serverProfilePda);
+      expect(profile.totalSlashed.toString()).to.equal(compensation.toString());
+
+      // Restore the bond so the server can keep hosting games
+      await program.methods
+        .topUpBond(compensation)
+        .accounts({ authority: gameServer.publicKey, gameServerProfile: serverProfilePda })
+        .signers([gameServer])
+        .rpc();
+    });
+  });
+
+  describe("Knockout tournaments", () => {
+    const player3 = Keypair.generate();
+    const bountyBps = 5000; // Half of each 1 SOL buy-in
+    const fee = (amount: number) => amount * feeBps / 10_000;
+
+    before(async () => {
+      await airdrop(player3, 10 * LAMPORTS_PER_SOL);
+    });
+
+    async function startKnockout(progressive: boolean) {
+      const { gameId, pokerEscrow } = newGame();
+      const bounty = await program.methods
+        .setBounty(bountyBps, progressive)
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+      await createGame(gameId, GameType.KnockoutTournament, [bounty]);
+      for (const player of [player1, player2, player3]) {
+        await joinGame(pokerEscrow, player);
+      }
+      await startGame(pokerEscrow);
+      return pokerEscrow;
+    }
+
+    function eliminate(pokerEscrow: PublicKey, player: Keypair, eliminator: Keypair) {
+      return program.methods
+        .eliminatePlayer(player.publicKey, eliminator.publicKey)
+        .accounts({
+          operator: gameServer.publicKey,
+          gameServer: serverProfilePda,
+          pokerEscrow,
+          payoutAccount: eliminator.publicKey,
+          treasury,
+          season: null,
+        })
+        .signers([gameServer])
+        .rpc();
+    }
+
+    // Pays out the whole pot to player 1, which only works once no bounty is held
+    async function payOutPrizePool(pokerEscrow: PublicKey) {
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.players.every((p) => p.bounty.isZero())).to.be.true;
+      await distributePot(
+        pokerEscrow,
+        player1.publicKey,
+        gameState.totalPot,
+        ratingAccounts([player1, player2, player3])
+      ).rpc();
+      const completed = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(completed.status).to.deep.equal({ completed: {} });
+    }
+
+    it("Pays the full bounty and releases the last player's", async () => {
+      const pokerEscrow = await startKnockout(false);
+      const bounty = LAMPORTS_PER_SOL * bountyBps / 10_000;
+
+      const balanceBefore = await provider.connection.getBalance(player1.publicKey);
+      await eliminate(pokerEscrow, player3, player1);
+      const balanceAfter = await provider.connection.getBalance(player1.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(bounty - fee(bounty));
+
+      // Bounties stay out of the prize pool while their players stand
+      await expectError(
+        distributePot(pokerEscrow, player1.publicKey, new anchor.BN(2.5 * LAMPORTS_PER_SOL)).rpc(),
+        "PayoutMismatch"
+      );
+
+      // Knocking out player 2 leaves player 1 alone, releasing their bounty
+      await eliminate(pokerEscrow, player2, player1);
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.totalPot.toNumber()).to.equal(3 * LAMPORTS_PER_SOL - 2 * bounty);
+      await payOutPrizePool(pokerEscrow);
+    });
+
+    it("Pays half the bounty when progressive, then releases the rest", async () => {
+      const pokerEscrow = await startKnockout(true);
+      const bounty = LAMPORTS_PER_SOL * bountyBps / 10_000;
+
+      const balanceBefore = await provider.connection.getBalance(player1.publicKey);
+      await eliminate(pokerEscrow, player2, player1);
+      const balanceAfter = await provider.connection.getBalance(player1.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(bounty / 2 - fee(bounty / 2));
+
+      // The other half went to the prize pool
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.totalPot.toNumber()).to.equal(3 * LAMPORTS_PER_SOL - bounty / 2);
+      expect(gameState.players.map((p) => p.bounty.toNumber())).to.deep.equal([bounty, 0, bounty]);
+
+      // Two players left chop the pot: their bounties go to the prize pool
+      await program.methods
+        .releaseBounties()
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .signers([gameServer])
+        .rpc();
+      await payOutPrizePool(pokerEscrow);
+    });
+  });
+
+  describe("Ratings", () => {
+    it("Rates tournament players by where they placed", async () => {
+      const player3 = Keypair.generate();
+      await airdrop(player3, 10 * LAMPORTS_PER_SOL);
+
+      const { gameId, pokerEscrow } = newGame();
+      await createGame(gameId, GameType.Tournament);
+      for (const player of [player1, player2, player3]) {
+        await joinGame(pokerEscrow, player);
+      }
+      await startGame(pokerEscrow);
+
+      // Player 3 busts first, then player 2
+      for (const player of [player3, player2]) {
+        await program.methods
+          .setSeatStatus(player.publicKey, { eliminated: {} })
+          .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+          .signers([gameServer])
+          .rpc();
+      }
+      const gameState = await program.account.pokerEscrow.fetch(pokerEscrow);
+      expect(gameState.players.map((p) => p.eliminated)).to.deep.equal([0, 2, 1]);
+
+      const players = [player1, player2, player3];
+      const ratingsOf = () =>
+        Promise.all(players.map((p) => program.account.playerRating.fetch(ratingPda(p.publicKey))));
+      const before = await ratingsOf();
+
+      // Rating accounts must be passed for every player, in join order
+      await expectError(
+        distributePot(pokerEscrow, player1.publicKey, gameState.totalPot, ratingAccounts([player2, player1, player3])).rpc(),
+        "RatingAccountsMismatch"
+      );
+      await distributePot(pokerEscrow, player1.publicKey, gameState.totalPot, ratingAccounts(players)).rpc();
+
+      // Neither loser won a hand, but outlasting player 3 still counts
+      const [first, second, third] = (await ratingsOf()).map((r, i) => r.rating - before[i].rating);
+      expect(first).to.be.greaterThan(0);
+      expect(third).to.be.lessThan(0);
+      expect(second).to.be.greaterThan(third);
+    });
+  });
+
+  // Games created after this score for the season, so it runs last
+  describe("Seasons", () => {
+    const seasonId = Math.floor(Date.now() / 1000) % 1_000_000;
+    const season = seasonPda(seasonId);
+    const rakeShareBps = 5000;
+
+    before(async () => {
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .createSeason(
+          seasonId,
+          new anchor.BN(now - 60),
+          new anchor.BN(now + 3600),
+          { perSolWon: new anchor.BN(100), perHandWon: new anchor.BN(10), tournamentWin: new anchor.BN(50) },
+          rakeShareBps,
+          [10_000]
+        )
+        .accounts({ admin: provider.publicKey })
+        .rpc();
+      await program.methods
+        .joinSeason()
+        .accounts({ player: player1.publicKey, season })
+        .signers([player1])
+        .rpc();
+    });
+
+    it("Credits the rake share and points on a settled payout", async () => {
+      const { gameId, pokerEscrow } = newGame();
+      const window = await program.methods
+        .setSettlementWindow(new anchor.BN(60), new anchor.BN(0))
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow })
+        .instruction();
+      await createGame(gameId, GameType.Tournament, [window]);
+      await joinGame(pokerEscrow, player1);
+      await joinGame(pokerEscrow, player2);
+      await startGame(pokerEscrow);
+
+      const settlement = settlementPda(pokerEscrow, 0);
+      await program.methods
+        .proposeSettlement([{ player: player1.publicKey, amount: buyInAmount }], 5, "Flush")
+        .accounts({ operator: gameServer.publicKey, gameServer: serverProfilePda, pokerEscrow, settlement })
+        .signers([gameServer])
+        .rpc();
+      await program.methods
+        .disputeSettlement()
+        .accounts({ player: player2.publicKey, gameServer: serverProfilePda, pokerEscrow, settlement })
+        .signers([player2])
+        .rpc();
+
+      // The season can't be left out to skip its share of the fee
+      const resolve = () =>
+        program.methods.resolveDispute(null).accounts({
+          arbiter: provider.publicKey,
+          gameServer: serverProfilePda,
+          serverAuthority: gameServer.publicKey,
+          pokerEscrow,
+          settlement,
+          disputer: player2.publicKey,
+          treasury,
+        });
+      await expectError(
+        resolve().accounts({ season: null }).remainingAccounts(winnerAccounts([player1])).rpc(),
+        "SeasonMismatch"
+      );
+
+      const seasonBefore = await program.account.season.fetch(season);
+      await resolve().accounts({ season }).remainingAccounts(winnerAccounts([player1], season)).rpc();
+
+      const fee = buyInAmount.toNumber() * feeBps / 10_000;
+      const seasonAfter = await program.account.season.fetch(season);
+      expect(seasonAfter.prizePool.sub(seasonBefore.prizePool).toNumber()).to.equal(fee * rakeShareBps / 10_000);
+
+      // 100 points per SOL won after the fee, plus 10 for the hand
+      const entry = await program.account.seasonEntry.fetch(seasonEntryPda(season, player1.publicKey));
+      expect(entry.points.toNumber()).to.equal(Math.floor(100 * (buyInAmount.toNumber() - fee) / LAMPORTS_PER_SOL) + 10);
+      expect(entry.handsWon).to.equal(1);
+      expect(seasonAfter.standings[0].player.toBase58()).to.equal(player1.publicKey.toBase58());
+    });
+
+    it("Holds season prizes while the program is paused", async () => {
+      await setPaused(true);
+      await expectError(
+        program.methods
+          .closeSeason()
+          .accounts({ admin: provider.publicKey, season, treasury })
+          .remainingAccounts([{ pubkey: player1.publicKey, isSigner: false, isWritable: true }])
+          .rpc(),
+        "ProgramPaused"
+      );
+      await setPaused(false);
+    });
+  });
+
+  // Fees go to the treasury vault once it is initialized, so this runs after every game
+  describe("Treasury withdrawals and guardian rotation", () => {
+    const guardian = Keypair.generate();
+    const [vault] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], program.programId);
+    const [withdrawalRequest] = PublicKey.findProgramAddressSync(
+      [Buffer.from("withdrawal"), vault.toBuffer()],
+      program.programId
+    );
+
+    it("Lets only the current guardian hand over the role", async () => {
+      // The admin starts out as guardian
+      await program.methods.setGuardian(guardian.publicKey).accounts({ guardian: provider.publicKey }).rpc();
+
+      // Once handed over, the admin can't take it back
+      await expectError(
+        program.methods.setGuardian(provider.publicKey).accounts({ guardian: provider.publicKey }).rpc(),
+        "UnauthorizedGuardian"
+      );
+      const config = await program.account.programConfig.fetch(
+        PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0]
+      );
+      expect(config.guardian.toBase58()).to.equal(guardian.publicKey.toBase58());
+    });
+
+    it("Holds a treasury withdrawal until it unlocks and lets the guardian cancel it", async () => {
+      await program.methods.initializeTreasury().accounts({ admin: provider.publicKey }).rpc();
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({ fromPubkey: provider.publicKey, toPubkey: vault, lamports: LAMPORTS_PER_SOL })
+        )
+      );
+
+      await program.methods
+        .requestTreasuryWithdrawal(provider.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 2))
+        .accounts({ admin: provider.publicKey })
+        .rpc();
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      expect(request.unlocksAt.sub(request.requestedAt).toNumber()).to.equal(2 * 24 * 60 * 60);
+
+      const execute = () =>
+        program.methods
+          .executeTreasuryWithdrawal()
+          .accounts({ withdrawalRequest, destination: provider.publicKey, requestedBy: provider.publicKey })
+          .rpc();
+      await expectError(execute(), "WithdrawalLocked");
+
+      // The guardian now holds the pause, and it stops the treasury too
+      await setPaused(true, guardian);
+      await expectError(execute(), "ProgramPaused");
+      await setPaused(false, guardian);
+
+      // Only the guardian overrules a withdrawal the admin requested
+      const cancel = (signer: Keypair | null) =>
+        program.methods
+          .cancelTreasuryWithdrawal()
+          .accounts({ guardian: signer ? signer.publicKey : provider.publicKey, withdrawalRequest, requestedBy: provider.publicKey })
+          .signers(signer ? [signer] : [])
+          .rpc();
+      await expectError(cancel(null), "UnauthorizedGuardian");
+      await cancel(guardian);
+      expect(await provider.connection.getAccountInfo(withdrawalRequest)).to.be.null;
+      await expectError(execute(), "AccountNotInitialized");
+    });
+
+    it("Queues fee and arbiter changes for the guardian to cancel", async () => {
+      const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+      const newArbiter = Keypair.generate().publicKey;
+      await program.methods
+        .updateConfig(null, 500, null, null, null, newArbiter)
+        .accounts({ admin: provider.publicKey })
+        .rpc();
+
+      // Nothing changes until the delay has passed
+      let config = await program.account.programConfig.fetch(configPda);
+      expect(config.feeBps).to.equal(feeBps);
+      expect(config.arbiter.toBase58()).to.equal(provider.publicKey.toBase58());
+      expect(config.pendingChange.appliesAt.sub(new anchor.BN(Math.floor(Date.now() / 1000))).toNumber())
+        .to.be.closeTo(2 * 24 * 60 * 60, 60);
+      await expectError(program.methods.applyConfigChange().rpc(), "ConfigChangeLocked");
+
+      // Only the guardian can call it off
+      await expectError(
+        program.methods.cancelConfigChange().accounts({ guardian: provider.publicKey }).rpc(),
+        "UnauthorizedGuardian"
+      );
+      await program.methods.cancelConfigChange().accounts({ guardian: guardian.publicKey }).signers([guardian]).rpc();
+      config = await program.account.programConfig.fetch(configPda);
+      expect(config.pendingChange).to.be.null;
+      await expectError(program.methods.applyConfigChange().rpc(), "NoPendingConfigChange");
+    });
+  });
+
+  // Hands the admin role back at the end, so it can run after everything else
+  describe("Admin handover", () => {
+    const newAdmin = Keypair.generate();
+    const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+
+    const transferAdmin = (admin: Keypair | null, nominee: PublicKey) =>
+      program.methods
+        .transferAdmin(nominee)
+        .accounts({ admin: admin ? admin.publicKey : provider.publicKey })
+        .signers(admin ? [admin] : [])
+        .rpc();
+    const acceptAdmin = (signer: Keypair | null) =>
+      program.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: signer ? signer.publicKey : provider.publicKey })
+        .signers(signer ? [signer] : [])
+        .rpc();
+    const updateConfig = (admin: Keypair | null) =>
+      program.methods
+        .updateConfig(null, null, null, new anchor.BN(100 * LAMPORTS_PER_SOL), null, null)
+        .accounts({ admin: admin ? admin.publicKey : provider.publicKey })
+        .signers(admin ? [admin] : [])
+        .rpc();
+
+    it("Only lets the nominee take over, and shuts out the old admin", async () => {
+      await transferAdmin(null, newAdmin.publicKey);
+
+      // A stranger can't take the nomination
+      await expectError(acceptAdmin(player1), "UnauthorizedAdmin");
+
+      await acceptAdmin(newAdmin);
+      const config = await program.account.programConfig.fetch(configPda);
+      expect(config.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+      expect(config.pendingAdmin).to.be.null;
+
+      // The old admin is out, the new one is in
+      await expectError(updateConfig(null), "UnauthorizedAdmin");
+      await expectError(transferAdmin(null, provider.publicKey), "UnauthorizedAdmin");
+      await updateConfig(newAdmin);
+
+      // Hand it back through the same two steps
+      await transferAdmin(newAdmin, provider.publicKey);
+      await acceptAdmin(null);
+      expect((await program.account.programConfig.fetch(configPda)).admin.toBase58())
+        .to.equal(provider.publicKey.toBase58());
+    });
+  });
+});